@@ -1,9 +1,9 @@
 extern crate greeks;
 
-// a token reserves
-const R_A: f32 = 6779.0;
-// b token reserves
-const R_B: f32 = 1.448;
+// base token (ETH) reserves
+const BASE_RESERVE: f32 = 1.448;
+// quote token (USDC) reserves
+const QUOTE_RESERVE: f32 = 6779.0;
 // lower range
 const P_A: f32 = 3747.0;
 // upper range
@@ -19,17 +19,14 @@ const E_DELTA: f32 = 1.4517521820181736;
 const E_GAMMA: f32 = 0.002435131811150409;
 
 fn main() {
-    let virtual_liquidity = greeks::virtual_liquidity(
-        P_A, P_B, R_B,
-        R_A, // note: its tough to figure out which token is what you are pricing your greeks in, sometimes you might need to switch them around
-    );
+    let virtual_liquidity = greeks::virtual_liquidity(P_A, P_B, BASE_RESERVE, QUOTE_RESERVE);
 
     println!("Expected virtual liquidity: {:2.2}", E_RV);
     println!("Actual virtual liquidity: {:2.2}", virtual_liquidity);
 
-    println!("{}, {}, {}", virtual_liquidity, P, P_B);
-    let delta = greeks::concentrated_delta(virtual_liquidity, P, P_B);
-    let gamma = greeks::concentrated_gamma(virtual_liquidity, P);
+    println!("{}, {}, {}, {}", virtual_liquidity, P, P_A, P_B);
+    let delta = greeks::concentrated_delta(virtual_liquidity, P, P_A, P_B);
+    let gamma = greeks::concentrated_gamma(virtual_liquidity, P, P_A, P_B);
 
     println!("Expected delta: {:2}", E_DELTA);
     println!("Actual delta: {:2}", delta);
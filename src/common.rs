@@ -1,5 +1,14 @@
 use std::f64::consts::PI;
 
+use price::{euro_call, euro_put};
+
+/// Whether an option gives the right to buy (call) or sell (put) the underlying
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
 pub fn d1(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
     let ln = (s0 / x).ln();
     let t_num = t * (r - q + (sigma.powf(2f64) / 2f64));
@@ -15,6 +24,70 @@ pub fn d2_d1(t: f64, sigma: f64, d1: f64) -> f64 {
     return d1 - (t.sqrt() * sigma);
 }
 
+/// Calculates the forward price of an underlying with a continuous dividend yield
+///
+/// # Arguments
+/// * `s0` - The underlying spot price
+/// * `t` - time to delivery as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+pub fn forward_price(s0: f64, t: f64, r: f64, q: f64) -> f64 {
+    s0 * ((r - q) * t).exp()
+}
+
+/// Calculates the annualized cost of carry implied by a spot and a forward (or futures) price
+///
+/// The inverse of `forward_price`, giving the `b = r - q` of the generalized Black-Scholes-Merton model.
+///
+/// # Arguments
+/// * `spot` - The underlying spot price
+/// * `forward` - The forward price for delivery at `t`
+/// * `t` - time to delivery as a percentage of the year
+pub fn implied_carry(spot: f64, forward: f64, t: f64) -> f64 {
+    (forward / spot).ln() / t
+}
+
+/// Calculates the strike of the put that mirrors a call under put-call symmetry
+///
+/// With a flat volatility a call struck at `strike` is worth `strike / forward` puts struck at `forward^2 / strike`,
+/// the reflection of the strike about the forward in log space. Static hedges of barriers are built from this.
+///
+/// # Arguments
+/// * `forward` - The forward price of the underlying to expiry
+/// * `strike` - The strike of the call (or of the put, as the map is its own inverse)
+pub fn pcs_equivalent_strike(forward: f64, strike: f64) -> f64 {
+    forward.powf(2.0) / strike
+}
+
+/// Calculates how far priced options are from put-call symmetry, zero up to numerical error
+///
+/// Returns `call(x) - (x / F) * put(F^2 / x)` with `F` the forward, both priced at the same volatility.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `x` - The strike price of the call
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn put_call_symmetry_residual(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let forward = forward_price(s0, t, r, q);
+    let mirror = pcs_equivalent_strike(forward, x);
+    euro_call(s0, x, t, r, q, sigma) - x / forward * euro_put(s0, mirror, t, r, q, sigma)
+}
+
+/// Converts a span of calendar time into volatility time under a five day trading week
+///
+/// Each weekend day carries `weekend_vol_weight` of a trading day's variance, so over whole weeks
+/// a calendar year holds `(5 + 2 * weekend_vol_weight) / 7` of a year of trading-day variance.
+///
+/// # Arguments
+/// * `calendar_t` - time as a percentage of the calendar year
+/// * `weekend_vol_weight` - variance of a weekend day relative to a trading day, 0 for none and 1 for calendar time
+pub fn business_time_fraction(calendar_t: f64, weekend_vol_weight: f64) -> f64 {
+    calendar_t * (5.0 + 2.0 * weekend_vol_weight) / 7.0
+}
+
 pub fn one_over_sqrt_pi() -> f64 {
     return 1.0 / (2.0 * PI).sqrt();
 }
@@ -23,43 +96,66 @@ pub fn one_over_sqrt_pi() -> f64 {
 mod tests {
 
     use common::*;
-
-    const UNDERLYING: f64 = 64.68;
-    const STRIKE: f64 = 65.00;
-    const VOL: f64 = 0.5051;
-    const INTEREST_RATE: f64 = 0.0150;
-    const DIV_YIELD: f64 = 0.0210;
-    const DAYS_PER_YEAR: f64 = 365.0;
-    const TIME_TO_EXPIRY: f64 = 23.0 / DAYS_PER_YEAR;
+    use test_fixtures::SAMPLE_PARAMS;
 
     const E_D1: f64 = 0.0214;
     const E_D2: f64 = -0.1053;
+    const E_FORWARD: f64 = 64.6556;
 
     #[test]
     fn test_d1() {
-        let d1 = d1(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let d1 = d1(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (d1 - E_D1).abs();
         assert!(abs < 0.001);
     }
 
     #[test]
     fn test_d2() {
-        let d2 = d2(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let d2 = d2(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (d2 - E_D2).abs();
         assert!(abs < 0.001);
     }
+
+    #[test]
+    fn test_forward_price() {
+        let p = SAMPLE_PARAMS;
+        let forward = forward_price(p.s0, p.t, p.r, p.q);
+        let abs = (forward - E_FORWARD).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_implied_carry() {
+        let p = SAMPLE_PARAMS;
+        let forward = forward_price(p.s0, p.t, p.r, p.q);
+        let carry = implied_carry(p.s0, forward, p.t);
+        assert!((carry - (p.r - p.q)).abs() < 1e-12);
+        // a futures in contango implies positive carry
+        assert!(implied_carry(p.s0, E_FORWARD + 1.0, p.t) > 0.0);
+    }
+
+    #[test]
+    fn test_business_time_fraction() {
+        let p = SAMPLE_PARAMS;
+        assert!((business_time_fraction(p.t, 1.0) - p.t).abs() < 1e-15);
+        let trading_only = business_time_fraction(7.0 / p.days_per_year, 0.0);
+        assert!((trading_only - 5.0 / p.days_per_year).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_put_call_symmetry() {
+        let p = SAMPLE_PARAMS;
+        let forward = forward_price(p.s0, p.t, p.r, p.q);
+        let mirror = pcs_equivalent_strike(forward, p.x);
+        // the reflection is about the forward in log space and undoes itself
+        assert!(((p.x / forward).ln() + (mirror / forward).ln()).abs() < 1e-12);
+        assert!((pcs_equivalent_strike(forward, mirror) - p.x).abs() < 1e-12);
+
+        for &x in [50.0, p.x, 80.0].iter() {
+            let residual = put_call_symmetry_residual(p.s0, x, p.t, p.r, p.q, p.sigma);
+            assert!(residual.abs() < 1e-6);
+        }
+    }
 }
@@ -0,0 +1,149 @@
+// Module containing a plain description of a European option contract
+use common::OptionType;
+use greeks::{gbsm_greeks, Greeks};
+use price::price;
+
+/// The inputs of a European option on an underlying with a continuous dividend yield
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionContract {
+    pub kind: OptionType,
+    /// The underlying price of the option
+    pub s0: f64,
+    /// The strike price of the option
+    pub x: f64,
+    /// time to expiration as a percentage of the year
+    pub t: f64,
+    /// continuously compounded risk-free interest rate
+    pub r: f64,
+    /// continuously compounded divident yield
+    pub q: f64,
+    /// volatility
+    pub sigma: f64,
+}
+
+impl OptionContract {
+    /// Creates a contract from its inputs
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `s0` - The underlying price of the option
+    /// * `x` - The strike price of the option
+    /// * `t` - time to expiration as a percentage of the year
+    /// * `r` - continuously compounded risk-free interest rate
+    /// * `q` - continuously compounded divident yield
+    /// * `sigma` - volatility
+    pub fn new(
+        kind: OptionType,
+        s0: f64,
+        x: f64,
+        t: f64,
+        r: f64,
+        q: f64,
+        sigma: f64,
+    ) -> OptionContract {
+        OptionContract {
+            kind,
+            s0,
+            x,
+            t,
+            r,
+            q,
+            sigma,
+        }
+    }
+
+    /// Black-Scholes price of the contract
+    pub fn price(&self) -> f64 {
+        price(
+            self.kind, self.s0, self.x, self.t, self.r, self.q, self.sigma,
+        )
+    }
+
+    /// Black-Scholes greeks of the contract
+    ///
+    /// # Arguments
+    /// * `days_per_year` - the number of calendar days in the year
+    pub fn greeks(&self, days_per_year: f64) -> Greeks {
+        gbsm_greeks(
+            self.kind,
+            self.s0,
+            self.x,
+            self.t,
+            self.r,
+            self.r - self.q,
+            self.sigma,
+            days_per_year,
+        )
+    }
+}
+
+/// Calculates the greeks of a contract at a later date, holding spot, volatility and rates unchanged
+///
+/// Shows how the risk of a position evolves through time decay alone.
+///
+/// # Arguments
+/// * `contract` - The option to roll
+/// * `new_t` - time to expiration at the later date as a percentage of the year
+/// * `days_per_year` - the number of calendar days in the year
+///
+/// # Panics
+/// If `new_t` is not positive or is beyond the contract's current time to expiry
+pub fn roll_forward(contract: &OptionContract, new_t: f64, days_per_year: f64) -> Greeks {
+    assert!(new_t > 0.0, "new_t must be positive, got {}", new_t);
+    assert!(
+        new_t <= contract.t,
+        "new_t = {} is beyond the time to expiry {}",
+        new_t,
+        contract.t
+    );
+    OptionContract {
+        t: new_t,
+        ..*contract
+    }
+    .greeks(days_per_year)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use contract::*;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const E_CALL: f64 = 3.105;
+    const E_PUT_DELTA: f64 = -0.4908;
+
+    #[test]
+    fn test_option_contract() {
+        let p = SAMPLE_PARAMS;
+        let call = OptionContract::new(OptionType::Call, p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((call.price() - E_CALL).abs() < 0.001);
+
+        let put = OptionContract {
+            kind: OptionType::Put,
+            ..call
+        };
+        assert!((put.greeks(p.days_per_year).delta - E_PUT_DELTA).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_roll_forward() {
+        let p = SAMPLE_PARAMS;
+        let call = OptionContract::new(OptionType::Call, p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let base = call.greeks(p.days_per_year);
+        assert_eq!(roll_forward(&call, p.t, p.days_per_year), base);
+
+        // an at the money option gains gamma and loses vega into expiry
+        let rolled = roll_forward(&call, 3.0 / p.days_per_year, p.days_per_year);
+        assert!(rolled.gamma > base.gamma);
+        assert!(rolled.vega < base.vega);
+    }
+
+    #[test]
+    #[should_panic(expected = "beyond the time to expiry")]
+    fn test_roll_forward_backwards() {
+        let p = SAMPLE_PARAMS;
+        let call = OptionContract::new(OptionType::Call, p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        roll_forward(&call, 2.0 * p.t, p.days_per_year);
+    }
+}
@@ -0,0 +1,245 @@
+// Module containing market structure measures aggregated across an option chain
+use common::OptionType;
+use greeks::gamma;
+use solvers::brent;
+use value::{call_at_expiry, put_at_expiry};
+
+/// Calculates the net dealer gamma exposure (GEX) of an option chain
+///
+/// Sums `gamma * open_interest * contract_multiplier * s0^2` over the chain, with calls counted positive and puts
+/// negative under the convention that dealers are long the calls and short the puts the market trades. The result
+/// is the dollar gamma of the dealer book, the change in its dollar delta for a move of the whole spot price.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `chain` - (strike, open interest, volatility, option type) of each line of the chain
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `contract_multiplier` - units of the underlying per contract
+pub fn gamma_exposure(
+    s0: f64,
+    chain: &[(f64, f64, f64, OptionType)],
+    t: f64,
+    r: f64,
+    q: f64,
+    contract_multiplier: f64,
+) -> f64 {
+    chain
+        .iter()
+        .map(|&(x, open_interest, sigma, kind)| {
+            let sign = match kind {
+                OptionType::Call => 1.0,
+                OptionType::Put => -1.0,
+            };
+            sign * gamma(s0, x, t, r, q, sigma) * open_interest * contract_multiplier * s0.powf(2.0)
+        })
+        .sum()
+}
+
+/// Finds the spot price between two bounds where the net dealer gamma exposure of a chain crosses zero
+///
+/// Solves `gamma_exposure` for zero with Brent's method, returning `None` when it has the same sign at both bounds.
+///
+/// # Arguments
+/// * `chain` - (strike, open interest, volatility, option type) of each line of the chain
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `contract_multiplier` - units of the underlying per contract
+/// * `low` - lowest spot price searched
+/// * `high` - highest spot price searched
+#[allow(clippy::too_many_arguments)]
+pub fn gamma_flip_spot(
+    chain: &[(f64, f64, f64, OptionType)],
+    t: f64,
+    r: f64,
+    q: f64,
+    contract_multiplier: f64,
+    low: f64,
+    high: f64,
+) -> Option<f64> {
+    let gex = |s0: f64| gamma_exposure(s0, chain, t, r, q, contract_multiplier);
+    brent(gex, low, high, 0.0)
+}
+
+/// Scans spot prices across a range for the level where the net dealer gamma exposure of a chain flips sign
+///
+/// Steps through `search_range` in increments of `step` and refines the first sign change found with
+/// `gamma_flip_spot`. Returns `None` when the exposure keeps one sign across the range, or when `step` is not a
+/// positive finite number. A chain can flip more than once, in which case the flip closest to the low end of the
+/// range is returned.
+///
+/// # Arguments
+/// * `chain` - (strike, open interest, volatility, option type) of each line of the chain
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `contract_multiplier` - units of the underlying per contract
+/// * `search_range` - (lowest, highest) spot price searched
+/// * `step` - spacing of the scan
+pub fn gamma_flip_level(
+    chain: &[(f64, f64, f64, OptionType)],
+    t: f64,
+    r: f64,
+    q: f64,
+    contract_multiplier: f64,
+    search_range: (f64, f64),
+    step: f64,
+) -> Option<f64> {
+    if !(step > 0.0 && step.is_finite()) {
+        return None;
+    }
+    let (low, high) = search_range;
+    let steps = ((high - low) / step).ceil() as usize;
+    (0..steps)
+        .map(|i| {
+            (
+                low + i as f64 * step,
+                (low + (i + 1) as f64 * step).min(high),
+            )
+        })
+        .find_map(|(lo, hi)| gamma_flip_spot(chain, t, r, q, contract_multiplier, lo, hi))
+}
+
+/// Finds the max pain strike of an expiry, the settlement price that minimizes the total payout to option holders
+///
+/// Each strike is tried as the settlement price and the intrinsic value of every call and put is summed, weighted
+/// by its open interest. Ties go to the lowest strike.
+///
+/// # Arguments
+/// * `strikes` - The strikes of the expiry
+/// * `call_oi` - call open interest at each strike
+/// * `put_oi` - put open interest at each strike
+pub fn max_pain(strikes: &[f64], call_oi: &[f64], put_oi: &[f64]) -> f64 {
+    assert_eq!(strikes.len(), call_oi.len());
+    assert_eq!(strikes.len(), put_oi.len());
+    let payout = |settlement: f64| -> f64 {
+        strikes
+            .iter()
+            .zip(call_oi.iter().zip(put_oi.iter()))
+            .map(|(&x, (&calls, &puts))| {
+                calls * call_at_expiry(settlement, x) + puts * put_at_expiry(settlement, x)
+            })
+            .sum()
+    };
+    strikes
+        .iter()
+        .map(|&settlement| (settlement, payout(settlement)))
+        .fold((f64::NAN, f64::INFINITY), |best, (settlement, total)| {
+            if total < best.1 {
+                (settlement, total)
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use exposure::*;
+    use greeks::gamma;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const MULTIPLIER: f64 = 100.0;
+
+    // heavy put open interest below spot and call open interest above it
+    fn chain() -> Vec<(f64, f64, f64, OptionType)> {
+        let p = SAMPLE_PARAMS;
+        vec![
+            (55.0, 3000.0, p.sigma, OptionType::Put),
+            (60.0, 2000.0, p.sigma, OptionType::Put),
+            (70.0, 2000.0, p.sigma, OptionType::Call),
+            (75.0, 3000.0, p.sigma, OptionType::Call),
+        ]
+    }
+
+    #[test]
+    fn test_gamma_exposure() {
+        let p = SAMPLE_PARAMS;
+        let s0 = 64.68;
+        let single = [(65.0, 1000.0, p.sigma, OptionType::Call)];
+        let gex = gamma_exposure(s0, &single, p.t, p.r, p.q, MULTIPLIER);
+        let expected = gamma(s0, 65.0, p.t, p.r, p.q, p.sigma) * 1000.0 * MULTIPLIER * s0.powf(2.0);
+        assert!((gex - expected).abs() < 1e-9);
+
+        // a put on the same line cancels the call
+        let straddle = [
+            (65.0, 1000.0, p.sigma, OptionType::Call),
+            (65.0, 1000.0, p.sigma, OptionType::Put),
+        ];
+        let net = gamma_exposure(s0, &straddle, p.t, p.r, p.q, MULTIPLIER);
+        assert!(net.abs() < 1e-9);
+
+        // dealers are short gamma near the put wall and long it near the call wall
+        let chain = chain();
+        let gex = |s0: f64| gamma_exposure(s0, &chain, p.t, p.r, p.q, MULTIPLIER);
+        assert!(gex(57.0) < 0.0);
+        assert!(gex(73.0) > 0.0);
+    }
+
+    #[test]
+    fn test_gamma_flip_spot() {
+        let p = SAMPLE_PARAMS;
+        let chain = chain();
+        let flip = gamma_flip_spot(&chain, p.t, p.r, p.q, MULTIPLIER, 55.0, 75.0).unwrap();
+        assert!(60.0 < flip && flip < 70.0);
+        let gex = gamma_exposure(flip, &chain, p.t, p.r, p.q, MULTIPLIER);
+        assert!(gex.abs() < 1e-6);
+
+        // no flip between bounds where the book is short gamma throughout
+        assert_eq!(
+            gamma_flip_spot(&chain, p.t, p.r, p.q, MULTIPLIER, 50.0, 58.0,),
+            None
+        );
+    }
+
+    #[test]
+    fn test_gamma_flip_level() {
+        let p = SAMPLE_PARAMS;
+        // matching put and call lines either side of spot have equal gamma where their d1 mirror each other,
+        // at the geometric mean of the strikes shifted down by e^(-sigma^2 * t / 2)
+        let (low_strike, high_strike): (f64, f64) = (55.0, 75.0);
+        let pair = [
+            (low_strike, 1000.0, p.sigma, OptionType::Put),
+            (high_strike, 1000.0, p.sigma, OptionType::Call),
+        ];
+        let expected = (low_strike * high_strike).sqrt() * (-p.sigma.powf(2.0) * p.t / 2.0).exp();
+        let flip = gamma_flip_level(&pair, p.t, 0.0, 0.0, MULTIPLIER, (40.0, 90.0), 1.0).unwrap();
+        assert!((flip - expected).abs() < 1e-9);
+
+        // the range is scanned so the bounds need not bracket the flip on their own
+        let chain = chain();
+        let scanned =
+            gamma_flip_level(&chain, p.t, p.r, p.q, MULTIPLIER, (40.0, 120.0), 0.5).unwrap();
+        let bracketed = gamma_flip_spot(&chain, p.t, p.r, p.q, MULTIPLIER, 55.0, 75.0).unwrap();
+        assert!((scanned - bracketed).abs() < 1e-9);
+
+        assert_eq!(
+            gamma_flip_level(&chain, p.t, p.r, p.q, MULTIPLIER, (50.0, 58.0), 0.5,),
+            None
+        );
+
+        // a step which cannot walk the range finds nothing rather than looping or allocating without bound
+        for &step in [0.0, -0.5, f64::NAN, f64::INFINITY].iter() {
+            let flip = gamma_flip_level(&chain, p.t, p.r, p.q, MULTIPLIER, (40.0, 120.0), step);
+            assert_eq!(flip, None);
+        }
+    }
+
+    #[test]
+    fn test_max_pain() {
+        let strikes = [50.0, 55.0, 60.0, 65.0, 70.0, 75.0, 80.0];
+        // mirrored call and put open interest settle at the central strike
+        let call_oi = [100.0, 200.0, 400.0, 800.0, 400.0, 200.0, 100.0];
+        let put_oi = call_oi;
+        assert_eq!(max_pain(&strikes, &call_oi, &put_oi), 65.0);
+
+        // heavy call open interest low in the chain drags max pain down to it
+        let call_oi = [100.0, 5000.0, 400.0, 800.0, 400.0, 200.0, 100.0];
+        assert_eq!(max_pain(&strikes, &call_oi, &put_oi), 55.0);
+    }
+}
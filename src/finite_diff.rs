@@ -0,0 +1,87 @@
+// Module containing a generic finite difference engine for numerical greeks
+
+/// Central finite difference of a pricer with respect to one of its inputs
+///
+/// The pricer takes its inputs as a slice so that any of them can be bumped by position. With `order` 1
+/// returns `(f(p + h) - f(p - h)) / 2h` and with `order` 2 returns `(f(p + h) - 2f(p) + f(p - h)) / h^2`.
+///
+/// # Arguments
+/// * `pricer` - Function from the inputs to a price
+/// * `params` - The inputs at which to differentiate
+/// * `index` - Position in `params` of the input to bump
+/// * `h` - Size of the bump
+/// * `order` - 1 for the first derivative, 2 for the second
+///
+/// # Panics
+/// If `order` is not 1 or 2, or `index` is out of bounds
+pub fn finite_diff(
+    pricer: impl Fn(&[f64]) -> f64,
+    params: &[f64],
+    index: usize,
+    h: f64,
+    order: u8,
+) -> f64 {
+    let mut bumped = params.to_vec();
+    bumped[index] = params[index] + h;
+    let up = pricer(&bumped);
+    bumped[index] = params[index] - h;
+    let down = pricer(&bumped);
+    match order {
+        1 => (up - down) / (2.0 * h),
+        2 => (up - 2.0 * pricer(params) + down) / h.powf(2.0),
+        _ => panic!("finite_diff supports order 1 or 2, got {}", order),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use finite_diff::*;
+    use greeks::*;
+    use price::euro_call;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const PARAMS: [f64; 6] = [
+        SAMPLE_PARAMS.s0,
+        SAMPLE_PARAMS.x,
+        SAMPLE_PARAMS.t,
+        SAMPLE_PARAMS.r,
+        SAMPLE_PARAMS.q,
+        SAMPLE_PARAMS.sigma,
+    ];
+
+    fn call(p: &[f64]) -> f64 {
+        euro_call(p[0], p[1], p[2], p[3], p[4], p[5])
+    }
+
+    #[test]
+    fn test_finite_diff_delta() {
+        let p = SAMPLE_PARAMS;
+        let delta = finite_diff(call, &PARAMS, 0, 0.1, 1);
+        let expected = delta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((delta - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_finite_diff_gamma() {
+        let p = SAMPLE_PARAMS;
+        let fd_gamma = finite_diff(call, &PARAMS, 0, 0.5, 2);
+        let expected = gamma(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((fd_gamma - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_finite_diff_vega() {
+        let p = SAMPLE_PARAMS;
+        // vega is quoted per volatility point
+        let fd_vega = finite_diff(call, &PARAMS, 5, 0.001, 1) / 100.0;
+        let expected = vega(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((fd_vega - expected).abs() < 0.00001);
+    }
+
+    #[test]
+    #[should_panic(expected = "order 1 or 2")]
+    fn test_finite_diff_third_order() {
+        finite_diff(call, &PARAMS, 0, 0.01, 3);
+    }
+}
@@ -0,0 +1,120 @@
+// Test-only property checks over randomly sampled market inputs
+// Inputs are drawn from the seeded `SplitMix64` generator so failures are reproducible,
+// and the reference pricer integrates the payoff numerically so it shares no code with `cnd`.
+use std::f64::consts::PI;
+
+use rand_core::RngCore;
+
+use greeks::*;
+use mc::SplitMix64;
+use price::*;
+
+const SAMPLES: usize = 2000;
+
+// Spreads draws of the shared generator over the parameter space
+struct Sampler {
+    rng: SplitMix64,
+}
+
+impl Sampler {
+    fn new(seed: u64) -> Sampler {
+        Sampler {
+            rng: SplitMix64(seed),
+        }
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        // top 53 bits give a uniform on [0, 1)
+        (self.rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn uniform(&mut self, lo: f64, hi: f64) -> f64 {
+        lo + (hi - lo) * self.next_f64()
+    }
+
+    // (s0, x, t, r, q, sigma)
+    fn market(&mut self) -> (f64, f64, f64, f64, f64, f64) {
+        let s0 = self.uniform(1.0, 5000.0);
+        let x = s0 * self.uniform(0.7, 1.4);
+        let t = self.uniform(7.0 / 365.0, 2.0);
+        let r = self.uniform(0.0, 0.1);
+        let q = self.uniform(0.0, 0.1);
+        let sigma = self.uniform(0.1, 1.5);
+        (s0, x, t, r, q, sigma)
+    }
+}
+
+// Discounted expected call payoff under the lognormal terminal distribution, by Simpson's rule
+fn reference_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let steps = 4000;
+    let (lo, hi) = (-10.0, 10.0);
+    let h = (hi - lo) / steps as f64;
+    let drift = (r - q - 0.5 * sigma * sigma) * t;
+    let integrand = |z: f64| {
+        let s_t = s0 * (drift + sigma * t.sqrt() * z).exp();
+        let density = (-0.5 * z * z).exp() / (2.0 * PI).sqrt();
+        (s_t - x).max(0.0) * density
+    };
+    let mut sum = integrand(lo) + integrand(hi);
+    for i in 1..steps {
+        let weight = if i % 2 == 0 { 2.0 } else { 4.0 };
+        sum += weight * integrand(lo + i as f64 * h);
+    }
+    (-r * t).exp() * sum * h / 3.0
+}
+
+#[test]
+fn test_fuzz_reference_call() {
+    let mut sampler = Sampler::new(0x5EED);
+    for _ in 0..SAMPLES / 10 {
+        let (s0, x, t, r, q, sigma) = sampler.market();
+        let price = euro_call(s0, x, t, r, q, sigma);
+        let reference = reference_call(s0, x, t, r, q, sigma);
+        assert!((price - reference).abs() < 1e-5 * (s0 + x));
+    }
+}
+
+#[test]
+fn test_fuzz_put_call_parity() {
+    let mut sampler = Sampler::new(1);
+    for _ in 0..SAMPLES {
+        let (s0, x, t, r, q, sigma) = sampler.market();
+        let call = euro_call(s0, x, t, r, q, sigma);
+        let put = euro_put(s0, x, t, r, q, sigma);
+        let parity = s0 * (-q * t).exp() - x * (-r * t).exp();
+        assert!((call - put - parity).abs() < 1e-9 * (s0 + x));
+    }
+}
+
+#[test]
+fn test_fuzz_delta_parity() {
+    let mut sampler = Sampler::new(2);
+    for _ in 0..SAMPLES {
+        let (s0, x, t, r, q, sigma) = sampler.market();
+        let call = delta_call(s0, x, t, r, q, sigma);
+        let put = delta_put(s0, x, t, r, q, sigma);
+        assert!((call - put - (-q * t).exp()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_fuzz_delta_bounds() {
+    let mut sampler = Sampler::new(3);
+    for _ in 0..SAMPLES {
+        let (s0, x, t, r, q, sigma) = sampler.market();
+        let call = delta_call(s0, x, t, r, q, sigma);
+        let put = delta_put(s0, x, t, r, q, sigma);
+        assert!((0.0..=1.0).contains(&call));
+        assert!((-1.0..=0.0).contains(&put));
+    }
+}
+
+#[test]
+fn test_fuzz_gamma_vega_positive() {
+    let mut sampler = Sampler::new(4);
+    for _ in 0..SAMPLES {
+        let (s0, x, t, r, q, sigma) = sampler.market();
+        assert!(gamma(s0, x, t, r, q, sigma) > 0.0);
+        assert!(vega(s0, x, t, r, q, sigma) > 0.0);
+    }
+}
@@ -0,0 +1,113 @@
+// Module containing greeks of geometric average rate (Asian) options
+// The Kemna-Vorst price is a generalized Black-Scholes-Merton price with adjusted volatility `sigma / sqrt(3)`
+// and adjusted carry `(r - q - sigma^2 / 6) / 2` (see `geometric_asian_call`), so spot greeks follow directly,
+// while vega must account for volatility entering both adjustments.
+use std::f64::consts::E;
+
+use common::*;
+use greeks::*;
+use price::{geometric_asian_carry, geometric_asian_vol};
+use stats::cnd;
+
+/// Calculates the delta of a geometric Asian call option
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn geometric_asian_delta_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let b = geometric_asian_carry(r, q, sigma);
+    gbsm_delta(OptionType::Call, s0, x, t, r, b, geometric_asian_vol(sigma))
+}
+
+/// Calculates the delta of a geometric Asian put option
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn geometric_asian_delta_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let b = geometric_asian_carry(r, q, sigma);
+    gbsm_delta(OptionType::Put, s0, x, t, r, b, geometric_asian_vol(sigma))
+}
+
+/// Calculates the vega of a geometric Asian option, per volatility point
+///
+/// Volatility moves both the adjusted volatility, by `1 / sqrt(3)`, and the adjusted carry, by `-sigma / 6`.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn geometric_asian_vega(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> f64 {
+    let b = geometric_asian_carry(r, q, sigma);
+    let sigma_a = geometric_asian_vol(sigma);
+    let d1 = d1(s0, x, t, b, 0.0, sigma_a);
+
+    // sensitivity of the price to the carry
+    let forward_leg = (1.0 / 100.0) * t * s0 * E.powf((b - r) * t);
+    let carry_sensitivity = match kind {
+        OptionType::Call => forward_leg * cnd(d1),
+        OptionType::Put => -forward_leg * cnd(-d1),
+    };
+
+    gbsm_vega(s0, x, t, r, b, sigma_a) / 3f64.sqrt() - carry_sensitivity * sigma / 6.0
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use greeks::*;
+    use price::*;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_geometric_asian_delta() {
+        let p = SAMPLE_PARAMS;
+        let h = 1e-4;
+        let price = |s0: f64| geometric_asian_call(s0, p.x, p.t, p.r, p.q, p.sigma);
+        let e_delta = (price(p.s0 + h) - price(p.s0 - h)) / (2.0 * h);
+        let delta_call = geometric_asian_delta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let delta_put = geometric_asian_delta_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((delta_call - e_delta).abs() < 0.001);
+        assert!(delta_put < 0.0);
+    }
+
+    #[test]
+    fn test_geometric_asian_vega() {
+        let p = SAMPLE_PARAMS;
+        for kind in [OptionType::Call, OptionType::Put].iter() {
+            let price = |sigma: f64| match *kind {
+                OptionType::Call => geometric_asian_call(p.s0, p.x, p.t, p.r, p.q, sigma),
+                OptionType::Put => geometric_asian_put(p.s0, p.x, p.t, p.r, p.q, sigma),
+            };
+            let h = 1e-5;
+            let e_vega = (price(p.sigma + h) - price(p.sigma - h)) / (2.0 * h) / 100.0;
+            let asian_vega = geometric_asian_vega(*kind, p.s0, p.x, p.t, p.r, p.q, p.sigma);
+            assert!((asian_vega - e_vega).abs() < 0.0001);
+
+            // averaging dampens the exposure to volatility
+            let vanilla_vega = vega(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+            assert!(asian_vega < vanilla_vega);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+// Module containing greeks of cash-or-nothing binary options
+use std::f64::consts::E;
+
+use common::*;
+use stats::pdf;
+
+/// Calculates the delta of a binary paying one unit of cash in the money
+///
+/// # Arguments
+/// * `kind` - Whether the binary pays above (call) or below (put) the strike
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn binary_delta(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d2 = d2(s0, x, t, r, q, sigma);
+    let pdf = pdf(d2);
+    let delta = E.powf(-r * t) * pdf / (s0 * sigma * t.sqrt());
+    match kind {
+        OptionType::Call => delta,
+        OptionType::Put => -delta,
+    }
+}
+
+/// Calculates the gamma of a binary paying one unit of cash in the money
+///
+/// # Arguments
+/// * `kind` - Whether the binary pays above (call) or below (put) the strike
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn binary_gamma(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    let pdf = pdf(d2);
+    let gamma = -E.powf(-r * t) * pdf * d1 / (s0.powf(2.0) * sigma.powf(2.0) * t);
+    match kind {
+        OptionType::Call => gamma,
+        OptionType::Put => -gamma,
+    }
+}
+
+/// Calculates the delta of a binary paying `cash_payout` in the money and `rebate` otherwise
+///
+/// The rebate leg is a bond, so only the difference `cash_payout - rebate` carries delta.
+///
+/// # Arguments
+/// * `kind` - Whether the binary pays above (call) or below (put) the strike
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `cash_payout` - cash paid if the binary finishes in the money
+/// * `rebate` - cash paid if the binary finishes out of the money
+#[allow(clippy::too_many_arguments)]
+pub fn binary_with_rebate_delta(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    cash_payout: f64,
+    rebate: f64,
+) -> f64 {
+    (cash_payout - rebate) * binary_delta(kind, s0, x, t, r, q, sigma)
+}
+
+/// Calculates the gamma of a binary paying `cash_payout` in the money and `rebate` otherwise
+///
+/// # Arguments
+/// * `kind` - Whether the binary pays above (call) or below (put) the strike
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `cash_payout` - cash paid if the binary finishes in the money
+/// * `rebate` - cash paid if the binary finishes out of the money
+#[allow(clippy::too_many_arguments)]
+pub fn binary_with_rebate_gamma(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    cash_payout: f64,
+    rebate: f64,
+) -> f64 {
+    (cash_payout - rebate) * binary_gamma(kind, s0, x, t, r, q, sigma)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use greeks::*;
+    use price::binary_with_rebate;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const CASH_PAYOUT: f64 = 10.0;
+    const REBATE: f64 = 2.0;
+
+    fn price(kind: OptionType, s0: f64) -> f64 {
+        let p = SAMPLE_PARAMS;
+        binary_with_rebate(kind, s0, p.x, p.t, p.r, p.q, p.sigma, CASH_PAYOUT, REBATE)
+    }
+
+    #[test]
+    fn test_binary_with_rebate_delta() {
+        let p = SAMPLE_PARAMS;
+        let h = 0.01;
+        for &kind in [OptionType::Call, OptionType::Put].iter() {
+            let delta = binary_with_rebate_delta(
+                kind,
+                p.s0,
+                p.x,
+                p.t,
+                p.r,
+                p.q,
+                p.sigma,
+                CASH_PAYOUT,
+                REBATE,
+            );
+            let expected = (price(kind, p.s0 + h) - price(kind, p.s0 - h)) / (2.0 * h);
+            assert!((delta - expected).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn test_binary_with_rebate_gamma() {
+        let p = SAMPLE_PARAMS;
+        let h = 0.01;
+        for &kind in [OptionType::Call, OptionType::Put].iter() {
+            let gamma = binary_with_rebate_gamma(
+                kind,
+                p.s0,
+                p.x,
+                p.t,
+                p.r,
+                p.q,
+                p.sigma,
+                CASH_PAYOUT,
+                REBATE,
+            );
+            let expected = (price(kind, p.s0 + h) - 2.0 * price(kind, p.s0)
+                + price(kind, p.s0 - h))
+                / h.powf(2.0);
+            assert!((gamma - expected).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_binary_with_no_rebate() {
+        let p = SAMPLE_PARAMS;
+        let delta = binary_with_rebate_delta(
+            OptionType::Call,
+            p.s0,
+            p.x,
+            p.t,
+            p.r,
+            p.q,
+            p.sigma,
+            1.0,
+            0.0,
+        );
+        let plain = binary_delta(OptionType::Call, p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert_eq!(delta, plain);
+    }
+}
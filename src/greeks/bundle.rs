@@ -0,0 +1,126 @@
+// Module containing a bundle of the standard greeks for a single position
+use std::ops::{Add, Mul};
+
+use common::OptionType;
+use greeks::{greeks_call, greeks_put};
+
+/// The standard first-order greeks and gamma of a position
+///
+/// Units follow the standalone functions: `theta` is per calendar day, `vega` and `rho` are per
+/// percentage point move in volatility and the interest rate respectively.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Greeks {
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub vega: f64,
+    pub rho: f64,
+}
+
+/// The second and third order greeks of a single option
+///
+/// Units follow the standalone functions: `charm`, `veta` and `color` are per calendar day, `vomma`, `veta` and
+/// `vera` are scaled by 1/100 alongside the per point `vega` and `rho`, and the rest are per unit move.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SecondOrderGreeks {
+    pub gamma: f64,
+    pub vanna: f64,
+    pub charm: f64,
+    pub vomma: f64,
+    pub veta: f64,
+    pub vera: f64,
+    pub speed: f64,
+    pub zomma: f64,
+    pub color: f64,
+}
+
+impl Greeks {
+    /// Calculates the Black-Scholes first-order greeks and gamma of a call or put option in one pass
+    ///
+    /// `d1` and `d2` are computed once and shared by every greek, see `greeks_call` and `greeks_put`. Only the
+    /// fields of `Greeks` are filled, the second order greeks such as vanna and charm come in one pass from
+    /// `second_order_greeks`.
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `s0` - The underlying price of the option
+    /// * `x` - The strike price of the option
+    /// * `t` - time to expiration as a percentage of the year
+    /// * `r` - continuously compounded risk-free interest rate
+    /// * `q` - continuously compounded divident yield
+    /// * `sigma` - volatility
+    /// * `days_per_year` - the number of calendar days in the year
+    #[allow(clippy::too_many_arguments)]
+    pub fn black_scholes(
+        kind: OptionType,
+        s0: f64,
+        x: f64,
+        t: f64,
+        r: f64,
+        q: f64,
+        sigma: f64,
+        days_per_year: f64,
+    ) -> Greeks {
+        match kind {
+            OptionType::Call => greeks_call(s0, x, t, r, q, sigma, days_per_year),
+            OptionType::Put => greeks_put(s0, x, t, r, q, sigma, days_per_year),
+        }
+    }
+}
+
+impl Add for Greeks {
+    type Output = Greeks;
+
+    fn add(self, other: Greeks) -> Greeks {
+        Greeks {
+            delta: self.delta + other.delta,
+            gamma: self.gamma + other.gamma,
+            theta: self.theta + other.theta,
+            vega: self.vega + other.vega,
+            rho: self.rho + other.rho,
+        }
+    }
+}
+
+/// Scales the greeks by a position size
+impl Mul<f64> for Greeks {
+    type Output = Greeks;
+
+    fn mul(self, quantity: f64) -> Greeks {
+        Greeks {
+            delta: self.delta * quantity,
+            gamma: self.gamma * quantity,
+            theta: self.theta * quantity,
+            vega: self.vega * quantity,
+            rho: self.rho * quantity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use greeks::*;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_black_scholes_matches_standalone() {
+        let p = SAMPLE_PARAMS;
+        let (s, t, r, q, v) = (p.s0, p.t, p.r, p.q, p.sigma);
+        for &x in [50.0, p.x, 80.0].iter() {
+            for &kind in [OptionType::Call, OptionType::Put].iter() {
+                let greeks = Greeks::black_scholes(kind, s, x, t, r, q, v, p.days_per_year);
+                let close = |bundled: f64, standalone: f64| (bundled - standalone).abs() < 1e-12;
+                assert!(close(greeks.delta, delta(kind, s, x, t, r, q, v)));
+                assert!(close(greeks.gamma, gamma(s, x, t, r, q, v)));
+                assert!(close(
+                    greeks.theta,
+                    theta(kind, s, x, t, r, q, v, p.days_per_year)
+                ));
+                assert!(close(greeks.vega, vega(s, x, t, r, q, v)));
+                assert!(close(greeks.rho, rho(kind, s, x, t, r, q, v)));
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+// Module containing greeks under the generalized Black-Scholes-Merton model
+// where the cost of carry `b` selects the pricing model (see `gbsm_price`):
+// `b = r` Black-Scholes, `b = r - q` Merton, `b = 0` Black-76, `b = r - rf` Garman-Kohlhagen
+use common::*;
+use greeks::*;
+use price::gbsm_price;
+
+/// Calculates the delta of an option under a generalized cost of carry
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+pub fn gbsm_delta(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, b: f64, sigma: f64) -> f64 {
+    delta(kind, s0, x, t, r, r - b, sigma)
+}
+
+/// Calculates the gamma of an option under a generalized cost of carry
+///
+/// # Arguments
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+pub fn gbsm_gamma(s0: f64, x: f64, t: f64, r: f64, b: f64, sigma: f64) -> f64 {
+    gamma(s0, x, t, r, r - b, sigma)
+}
+
+/// Calculates the theta of an option under a generalized cost of carry, per calendar day
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+#[allow(clippy::too_many_arguments)]
+pub fn gbsm_theta(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    b: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> f64 {
+    match kind {
+        OptionType::Call => theta_call_carry(s0, x, t, r, b, sigma, days_per_year),
+        OptionType::Put => theta_put_carry(s0, x, t, r, b, sigma, days_per_year),
+    }
+}
+
+/// Calculates the vega of an option under a generalized cost of carry, per volatility point
+///
+/// # Arguments
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+pub fn gbsm_vega(s0: f64, x: f64, t: f64, r: f64, b: f64, sigma: f64) -> f64 {
+    vega(s0, x, t, r, r - b, sigma)
+}
+
+/// Calculates the rho of an option under a generalized cost of carry, per percentage point
+///
+/// The carry moves one for one with the rate while the dividend yield (or foreign rate) `r - b` is held, as for
+/// Black-Scholes, Merton and Garman-Kohlhagen. For a futures option, where the carry is pinned at zero and the rate
+/// only discounts, use `black76_rho`.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+pub fn gbsm_rho(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, b: f64, sigma: f64) -> f64 {
+    rho(kind, s0, x, t, r, r - b, sigma)
+}
+
+/// Calculates the rho of an option on a futures price under Black-76, per percentage point
+///
+/// The futures price does not move with the rate, which only enters through discounting the payoff, so rho is
+/// `-t * price / 100` for calls and puts alike.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `f` - The futures price of the underlying
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `sigma` - volatility
+pub fn black76_rho(kind: OptionType, f: f64, x: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    -t * gbsm_price(kind, f, x, t, r, 0.0, sigma) / 100.0
+}
+
+/// Calculates delta, gamma, theta, vega and rho of an option under a generalized cost of carry
+///
+/// Rho is `gbsm_rho`, with the carry moving with the rate.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+#[allow(clippy::too_many_arguments)]
+pub fn gbsm_greeks(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    b: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> Greeks {
+    Greeks {
+        delta: gbsm_delta(kind, s0, x, t, r, b, sigma),
+        gamma: gbsm_gamma(s0, x, t, r, b, sigma),
+        theta: gbsm_theta(kind, s0, x, t, r, b, sigma, days_per_year),
+        vega: gbsm_vega(s0, x, t, r, b, sigma),
+        rho: gbsm_rho(kind, s0, x, t, r, b, sigma),
+    }
+}
+
+/// Sensitivities of an option to the two legs of its carry, the interest rate and the dividend yield
+///
+/// Both are per percentage point move, like the standalone `rho_*` and `epsilon_*` functions.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CarrySens {
+    pub rho: f64,
+    pub epsilon: f64,
+}
+
+/// Calculates the rate and dividend yield sensitivities of an option together
+///
+/// A parallel move in both leaves the carry `r - q` unchanged, so `rho + epsilon` is the sensitivity to the level of
+/// rates at a fixed carry while their difference drives carry trades.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn carry_sensitivities(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> CarrySens {
+    let epsilon = match kind {
+        OptionType::Call => epsilon_call(s0, x, t, r, q, sigma),
+        OptionType::Put => epsilon_put(s0, x, t, r, q, sigma),
+    };
+    CarrySens {
+        rho: rho(kind, s0, x, t, r, q, sigma),
+        epsilon,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use greeks::*;
+    use price::{euro_call, euro_put, gbsm_price};
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_gbsm_call_greeks() {
+        let p = SAMPLE_PARAMS;
+        let greeks = gbsm_greeks(
+            OptionType::Call,
+            p.s0,
+            p.x,
+            p.t,
+            p.r,
+            p.r - p.q,
+            p.sigma,
+            p.days_per_year,
+        );
+        assert!((greeks.delta - p.call_delta).abs() < 0.001);
+        assert!((greeks.gamma - p.gamma).abs() < 0.001);
+        assert!((greeks.theta - p.theta_call).abs() < 0.001);
+        assert!((greeks.vega - p.vega).abs() < 0.001);
+        assert!((greeks.rho - p.rho_call).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_gbsm_put_greeks() {
+        let p = SAMPLE_PARAMS;
+        let greeks = gbsm_greeks(
+            OptionType::Put,
+            p.s0,
+            p.x,
+            p.t,
+            p.r,
+            p.r - p.q,
+            p.sigma,
+            p.days_per_year,
+        );
+        assert!((greeks.delta - p.put_delta).abs() < 0.001);
+        assert!((greeks.gamma - p.gamma).abs() < 0.001);
+        assert!((greeks.theta - p.theta_put).abs() < 0.001);
+        assert!((greeks.vega - p.vega).abs() < 0.001);
+        assert!((greeks.rho - p.rho_put).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_gbsm_black_76_delta() {
+        // futures option delta is the discounted N(d1)
+        let p = SAMPLE_PARAMS;
+        let delta = gbsm_delta(OptionType::Call, p.s0, p.x, p.t, p.r, 0.0, p.sigma);
+        let undiscounted = delta_call(p.s0, p.x, p.t, 0.0, 0.0, p.sigma);
+        let e_delta = (-p.r * p.t).exp() * undiscounted;
+        let abs = (delta - e_delta).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_black76_rho() {
+        // with the carry pinned at zero a rate bump only changes the discount factor
+        let p = SAMPLE_PARAMS;
+        let h = 1e-4;
+        for kind in [OptionType::Call, OptionType::Put].iter() {
+            let rho = black76_rho(*kind, p.s0, p.x, p.t, p.r, p.sigma);
+            let price = |r: f64| gbsm_price(*kind, p.s0, p.x, p.t, r, 0.0, p.sigma);
+            let bump = (price(p.r + h) - price(p.r - h)) / (2.0 * h);
+            assert!((rho - bump / 100.0).abs() < 1e-6);
+            assert!(rho < 0.0);
+        }
+    }
+
+    #[test]
+    fn test_gbsm_rho_zero_carry() {
+        // an equity with r == q also has zero carry, but its rate bump moves the carry and not just the discounting
+        let p = SAMPLE_PARAMS;
+        let h = 1e-4;
+        let rho = gbsm_rho(OptionType::Call, p.s0, p.x, p.t, p.r, 0.0, p.sigma);
+        let price = |r: f64| euro_call(p.s0, p.x, p.t, r, p.r, p.sigma);
+        let bump = (price(p.r + h) - price(p.r - h)) / (2.0 * h);
+        assert!((rho - bump / 100.0).abs() < 1e-6);
+        assert!(rho > 0.0);
+    }
+
+    #[test]
+    fn test_carry_sensitivities() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let call = carry_sensitivities(OptionType::Call, s, x, t, r, q, v);
+        let put = carry_sensitivities(OptionType::Put, s, x, t, r, q, v);
+        assert_eq!(call.rho, rho_call(s, x, t, r, q, v));
+        assert_eq!(put.rho, rho_put(s, x, t, r, q, v));
+        assert!((call.rho - p.rho_call).abs() < 0.001);
+        assert!((put.rho - p.rho_put).abs() < 0.001);
+        // epsilon is the per point change in price for a move in the dividend yield
+        let h = 1e-4;
+        let call_bump =
+            (euro_call(s, x, t, r, q + h, v) - euro_call(s, x, t, r, q - h, v)) / (2.0 * h);
+        let put_bump =
+            (euro_put(s, x, t, r, q + h, v) - euro_put(s, x, t, r, q - h, v)) / (2.0 * h);
+        assert!((call.epsilon - call_bump / 100.0).abs() < 1e-4);
+        assert!((put.epsilon - put_bump / 100.0).abs() < 1e-4);
+        assert!(call.epsilon < 0.0 && put.epsilon > 0.0);
+
+        // put-call parity pins the difference of the two options' epsilons to the forward's, -s0 t e^(-qt) / 100
+        let forward = -s * t * (-q * t).exp() / 100.0;
+        assert!((call.epsilon - put.epsilon - forward).abs() < 1e-12);
+    }
+}
@@ -10,71 +10,529 @@
 // Cavets with greeks and concentrated liquididty ranges
 // Positions have no gamma outside of the range
 
+use greeks::Greeks;
+use solvers::brent;
+use std::fmt;
+
 /// Calculates virtual liquidity of a concentrated liquidity share
 /// Refer to https://gist.github.com/0xperp/fe5327d05b59c9122332d860adf2ba42 for a python notebook on the formulas
+///
+/// Prices are quoted as units of the quote token per base token, e.g. USDC per ETH, so the base token is the one
+/// being priced (token0 in Uniswap terms) and the quote token is the numeraire (token1).
 /// # Arguments
 /// * `p_a` - Lower Tick range
 /// * `p_b` - Upper tick range
-/// * `R_a` - Reserves of token a
-/// * `R_b` - Reserves of token b
+/// * `base_reserve` - Reserves of the base token, e.g. ETH
+/// * `quote_reserve` - Reserves of the quote token, e.g. USDC
 /// # Return
 /// * virtual liquidity
-pub fn virtual_liquidity(p_a: f32, p_b: f32, r_a: f32, r_b: f32) -> f32 {
+pub fn virtual_liquidity(p_a: f32, p_b: f32, base_reserve: f32, quote_reserve: f32) -> f32 {
+    liquidity_for_reserves(
+        p_a as f64,
+        p_b as f64,
+        base_reserve as f64,
+        quote_reserve as f64,
+    ) as f32
+}
+
+// Virtual liquidity of a share holding 'base_reserve' and 'quote_reserve' over the range, in f64 for the position
+// types and conversions built on `virtual_liquidity`
+fn liquidity_for_reserves(p_a: f64, p_b: f64, base_reserve: f64, quote_reserve: f64) -> f64 {
+    // single-sided deposits sit on a boundary of the range, where only one token is held:
+    // at or below p_a the share is all base token, at or above p_b it is all quote token
+    if quote_reserve == 0.0 {
+        return base_reserve / (1.0 / p_a.sqrt() - 1.0 / p_b.sqrt());
+    }
+    if base_reserve == 0.0 {
+        return quote_reserve / (p_b.sqrt() - p_a.sqrt());
+    }
+
     // solving "bounded liquidity position" eq. 1 for L
     // terms for quadratic eq.
-    let a = (p_a.sqrt() / p_b.sqrt()) - 1_f32;
-    let b = (r_b / p_b.sqrt()) + (r_a * p_a.sqrt());
-    let c = r_a * r_b;
+    let a = (p_a.sqrt() / p_b.sqrt()) - 1.0;
+    let b = (quote_reserve / p_b.sqrt()) + (base_reserve * p_a.sqrt());
+    let c = base_reserve * quote_reserve;
 
     // discriminant
-    let d = b.powf(2.0) - (4_f32 * a * c);
+    let d = b.powf(2.0) - (4.0 * a * c);
 
     // solutions
     let solution1 = (-b - d.sqrt()) / (2.0 * a);
     let solution2 = (-b + d.sqrt()) / (2.0 * a);
 
-    // virtual reserves
-    let r_v = {
-        if solution1 > 0.0 {
-            solution1
-        } else {
-            solution2
-        }
-    };
-    r_v
+    // a < 0 and c > 0 so the roots have opposite signs and exactly one is positive
+    if solution1 > 0.0 {
+        solution1
+    } else {
+        solution2
+    }
 }
 
 /// Calculates delta of a concentrated liquidity share
 /// Refer to https://gist.github.com/0xperp/fe5327d05b59c9122332d860adf2ba42 for a python notebook on the formulas
+///
+/// Below the range the share is entirely the base token so delta stays at its maximum, and above the range
+/// it is entirely the quote token so delta is zero.
 /// # Arguments
 /// * `L` - Virtual Liquidity
-/// * `p_b` - Upper tick range
 /// * `p` - Current price
+/// * `p_a` - Lower tick range
+/// * `p_b` - Upper tick range
 /// # Return
 /// * delta
-pub fn concentrated_delta(l: f32, p: f32, p_b: f32) -> f32 {
-    l * (1.0 / p.sqrt() - 1.0 / p_b.sqrt())
+pub fn concentrated_delta(l: f32, p: f32, p_a: f32, p_b: f32) -> f32 {
+    let p_c = p.max(p_a).min(p_b);
+    l * (1.0 / p_c.sqrt() - 1.0 / p_b.sqrt())
 }
 
 /// Calculates gamma of a concentrated liquidity share
 /// Refer to https://gist.github.com/0xperp/fe5327d05b59c9122332d860adf2ba42 for a python notebook on the formulas
+///
+/// Zero outside of the range, where the share holds a single token.
 /// # Arguments
 /// * `L` - Virtual Liquidity
 /// * `p` - Current price
+/// * `p_a` - Lower tick range
+/// * `p_b` - Upper tick range
 /// # Return
 /// * gamma
-pub fn concentrated_gamma(l: f32, p: f32) -> f32 {
+pub fn concentrated_gamma(l: f32, p: f32, p_a: f32, p_b: f32) -> f32 {
+    gamma_in_range(l as f64, p as f64, p_a as f64, p_b as f64) as f32
+}
+
+// Gamma of a share with liquidity 'l', in f64 for the position types built on `concentrated_gamma`
+fn gamma_in_range(l: f64, p: f64, p_a: f64, p_b: f64) -> f64 {
+    if p < p_a || p > p_b {
+        return 0.0;
+    }
     0.5 * l * p.powf(-1.5)
 }
 
+/// Calculates the value of a concentrated liquidity share in the quote token
+///
+/// Holds `L * (1 / sqrt(p) - 1 / sqrt(p_b))` of the base token and `L * (sqrt(p) - sqrt(p_a))` of the quote token,
+/// with `p` clamped to the range: below it the share is all base token and above it all quote token.
+/// # Arguments
+/// * `L` - Virtual Liquidity
+/// * `p` - Current price
+/// * `p_a` - Lower tick range
+/// * `p_b` - Upper tick range
+/// # Return
+/// * value in units of the quote token
+pub fn concentrated_value(l: f32, p: f32, p_a: f32, p_b: f32) -> f32 {
+    let p_c = p.max(p_a).min(p_b);
+    let base = l * (1.0 / p_c.sqrt() - 1.0 / p_b.sqrt());
+    let quote = l * (p_c.sqrt() - p_a.sqrt());
+    base * p + quote
+}
+
+/// Calculates the token amounts needed to mint a concentrated liquidity share, the inverse of `virtual_liquidity`
+///
+/// Below the range only the base token is needed and above it only the quote token.
+/// # Arguments
+/// * `L` - Target virtual liquidity
+/// * `p` - Current price
+/// * `p_a` - Lower tick range
+/// * `p_b` - Upper tick range
+/// # Return
+/// * (base token amount, quote token amount)
+pub fn cl_amounts_for_liquidity(l: f64, p: f64, p_a: f64, p_b: f64) -> (f64, f64) {
+    let p_c = p.max(p_a).min(p_b);
+    let base = l * (1.0 / p_c.sqrt() - 1.0 / p_b.sqrt());
+    let quote = l * (p_c.sqrt() - p_a.sqrt());
+    (base, quote)
+}
+
+/// Calculates the v3 liquidity a Uniswap v2 position buys when its tokens are redeposited into a range
+///
+/// The v2 position is withdrawn at its own price `quote_reserve / base_reserve`, its value rebalanced into the token split the range
+/// needs at that price, and minted. Liquidity scales the amounts linearly, so the result is the value over that of a
+/// unit of liquidity. A v2 position is a v3 share over `(0, inf)` with
+/// liquidity `sqrt(base_reserve * quote_reserve)`, so narrower ranges return more liquidity for the same capital.
+/// # Arguments
+/// * `base_reserve` - Reserves of the base token in the v2 pool
+/// * `quote_reserve` - Reserves of the quote token in the v2 pool
+/// * `p_a` - Lower tick range
+/// * `p_b` - Upper tick range
+/// # Return
+/// * virtual liquidity of the equivalent v3 share
+pub fn v2_to_v3_equivalent(base_reserve: f64, quote_reserve: f64, p_a: f64, p_b: f64) -> f64 {
+    let p = quote_reserve / base_reserve;
+    let value = base_reserve * p + quote_reserve;
+    let (unit_base, unit_quote) = cl_amounts_for_liquidity(1.0, p, p_a, p_b);
+    value / (unit_base * p + unit_quote)
+}
+
+/// Checks whether a v3 range behaves like a full range v2 position
+///
+/// Compares the liquidity the v2 reserves would buy in the range with the v2 liquidity `sqrt(base_reserve * quote_reserve)`. The ratio
+/// is the capital efficiency of the range, and it approaches one as the range widens toward `(0, inf)`.
+/// # Arguments
+/// * `base_reserve` - Reserves of the base token in the v2 pool
+/// * `quote_reserve` - Reserves of the quote token in the v2 pool
+/// * `p_a` - Lower tick range
+/// * `p_b` - Upper tick range
+/// * `tolerance` - Largest relative excess of v3 liquidity still treated as full range
+/// # Return
+/// * whether the range is effectively full range
+pub fn v3_full_range_check(
+    base_reserve: f64,
+    quote_reserve: f64,
+    p_a: f64,
+    p_b: f64,
+    tolerance: f64,
+) -> bool {
+    let v2_liquidity = (base_reserve * quote_reserve).sqrt();
+    v2_to_v3_equivalent(base_reserve, quote_reserve, p_a, p_b) / v2_liquidity - 1.0 <= tolerance
+}
+
+// Mean and variance of the shortfall of the share against holding its initial tokens after 't' years of zero drift
+// geometric Brownian motion with volatility 'sigma', integrated over the terminal price with Simpson's rule
+fn impermanent_loss_moments(p: f64, p_a: f64, p_b: f64, sigma: f64, t: f64) -> (f64, f64) {
+    // liquidity scales every term, so work with a unit share
+    let (base, quote) = (1.0 / p.sqrt() - 1.0 / p_b.sqrt(), p.sqrt() - p_a.sqrt());
+    let steps = 400;
+    let (lo, hi) = (-8.0, 8.0);
+    let h = (hi - lo) / steps as f64;
+    let (mut sum, mut sum_sq) = (0.0, 0.0);
+    for i in 0..=steps {
+        let z = lo + i as f64 * h;
+        let p_t = p * (-sigma.powf(2.0) * t / 2.0 + sigma * t.sqrt() * z).exp();
+        let hold = base * p_t + quote;
+        let (lp_base, lp_quote) = cl_amounts_for_liquidity(1.0, p_t, p_a, p_b);
+        let loss = hold - (lp_base * p_t + lp_quote);
+        let weight = if i == 0 || i == steps {
+            1.0
+        } else if i % 2 == 1 {
+            4.0
+        } else {
+            2.0
+        };
+        let density = weight * (-z * z / 2.0).exp();
+        sum += density * loss;
+        sum_sq += density * loss.powf(2.0);
+    }
+    let norm = h / 3.0 / (2.0 * std::f64::consts::PI).sqrt();
+    let mean = sum * norm;
+    (mean, sum_sq * norm - mean.powf(2.0))
+}
+
+// Expected impermanent loss of a unit share over 't' years of zero drift geometric Brownian motion, accrued as the
+// gamma rent 0.5 * gamma * p^2 * sigma^2 at each instant and averaged over the lognormal price at that instant.
+// Simpson's rule in both time and the standard normal draw
+fn expected_gamma_rent(p: f64, p_a: f64, p_b: f64, sigma: f64, t: f64) -> f64 {
+    let simpson = |i: usize, n: usize| {
+        if i == 0 || i == n {
+            1.0
+        } else if i % 2 == 1 {
+            4.0
+        } else {
+            2.0
+        }
+    };
+    let (time_steps, price_steps) = (40, 400);
+    let (lo, hi) = (-8.0, 8.0);
+    let h = (hi - lo) / price_steps as f64;
+    let rent_at = |s: f64| -> f64 {
+        let sum: f64 = (0..=price_steps)
+            .map(|i| {
+                let z = lo + i as f64 * h;
+                let p_s = p * (-sigma.powf(2.0) * s / 2.0 + sigma * s.sqrt() * z).exp();
+                let rent =
+                    0.5 * gamma_in_range(1.0, p_s, p_a, p_b) * p_s.powf(2.0) * sigma.powf(2.0);
+                simpson(i, price_steps) * rent * (-z * z / 2.0).exp()
+            })
+            .sum();
+        sum * h / 3.0 / (2.0 * std::f64::consts::PI).sqrt()
+    };
+    let dt = t / time_steps as f64;
+    (0..=time_steps)
+        .map(|i| simpson(i, time_steps) * rent_at(i as f64 * dt))
+        .sum::<f64>()
+        * dt
+        / 3.0
+}
+
+/// Calculates the volatility at which the fees of a range exactly pay for its impermanent loss
+///
+/// Fees accrue at `fee_apr` on the value of the share over `t` years, while the share loses the gamma rent
+/// `concentrated_gamma * p^2 * sigma^2 / 2` against holding its tokens at each instant. The expected loss averages that
+/// rent over where a zero drift price can be at each point of the holding period, so ranges the price may leave lose
+/// less than today's gamma implies. Realized volatility below the result makes the range profitable.
+/// Returns `NaN` if even a volatility of 1000% does not consume the fees.
+/// # Arguments
+/// * `p` - Current price
+/// * `p_a` - Lower tick range
+/// * `p_b` - Upper tick range
+/// * `fee_apr` - Annual fee income as a fraction of the share's value
+/// * `t` - Holding period as a percentage of the year
+/// # Return
+/// * breakeven volatility
+pub fn cl_breakeven_vol(p: f64, p_a: f64, p_b: f64, fee_apr: f64, t: f64) -> f64 {
+    let (base, quote) = cl_amounts_for_liquidity(1.0, p, p_a, p_b);
+    let fees = fee_apr * t * (base * p + quote);
+    let shortfall = |sigma: f64| expected_gamma_rent(p, p_a, p_b, sigma, t) - fees;
+    brent(shortfall, 0.0, 10.0, 1e-12).unwrap_or(f64::NAN)
+}
+
+// Expected fraction of 't' years before driftless Brownian motion in log price with volatility 'sigma' first leaves
+// a range a factor 'k' either side of its start, from the eigenfunction series of the double barrier survival
+// probability 4 / pi * sum (-1)^m / n * e^(-lambda_n * s) over odd n = 2m + 1
+fn fraction_before_exit(k: f64, sigma: f64, t: f64) -> f64 {
+    let width = k.ln();
+    let mut sum = 0.0;
+    for m in 0..500 {
+        let n = (2 * m + 1) as f64;
+        let lambda = (n * std::f64::consts::PI * sigma / width).powf(2.0) / 8.0;
+        let sign = if m % 2 == 0 { 1.0 } else { -1.0 };
+        sum += sign / n * (1.0 - (-lambda * t).exp()) / lambda;
+    }
+    4.0 / std::f64::consts::PI * sum / t
+}
+
+/// Calculates the range around the current price that maximizes fees less a risk adjusted impermanent loss
+///
+/// Ranges are symmetric in log price, `[p / k, p * k]`. Per unit of capital a range earns the full range fee rate
+/// scaled by its capital efficiency over a v2 position until the price first leaves it, as a managed position would
+/// be withdrawn then. It pays its expected impermanent loss at `t` under a zero drift GBM plus `risk_aversion` times
+/// the variance of that loss. Narrow ranges earn more per unit of time but are exited sooner, so higher volatility
+/// pushes the optimum wider.
+/// # Arguments
+/// * `p` - Current price
+/// * `sigma` - Volatility of the price
+/// * `t` - Holding period as a percentage of the year
+/// * `fee_apr` - Annual fee income of a full range position as a fraction of its value
+/// * `risk_aversion` - Weight on the variance of impermanent loss per unit of capital, zero for risk neutral
+/// # Return
+/// * (lower price, upper price) of the optimal range
+pub fn optimal_cl_range(
+    p: f64,
+    sigma: f64,
+    t: f64,
+    fee_apr: f64,
+    risk_aversion: f64,
+) -> (f64, f64) {
+    // a full range share holds liquidity sqrt(p) / 2 per unit of value
+    let v2_liquidity = 1.0 / (2.0 * p.sqrt());
+    let objective = |width: f64| {
+        let k = width.exp();
+        let (p_a, p_b) = (p / k, p * k);
+        let (base, quote) = cl_amounts_for_liquidity(1.0, p, p_a, p_b);
+        let value = base * p + quote;
+        let efficiency = 1.0 / value / v2_liquidity;
+        let fees = fee_apr * t * efficiency * fraction_before_exit(k, sigma, t);
+        let (loss, loss_variance) = impermanent_loss_moments(p, p_a, p_b, sigma, t);
+        fees - loss / value - risk_aversion * loss_variance / value.powf(2.0)
+    };
+
+    // coarse search over log price widths `ln(k)` from 0.001 to 3 spaced geometrically, i.e. ranges from 0.1% to
+    // 20x either side, then a golden section search around the best
+    let grid = 100;
+    let (min_log, max_log) = (0.001_f64.ln(), 3.0_f64.ln());
+    let step = (max_log - min_log) / grid as f64;
+    let best = (0..=grid)
+        .map(|i| min_log + i as f64 * step)
+        .map(|x| (x, objective(x.exp())))
+        .fold((min_log, f64::NEG_INFINITY), |acc, (x, v)| {
+            if v > acc.1 {
+                (x, v)
+            } else {
+                acc
+            }
+        });
+    let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = ((best.0 - step).max(min_log), (best.0 + step).min(max_log));
+    for _ in 0..40 {
+        let (x1, x2) = (hi - ratio * (hi - lo), lo + ratio * (hi - lo));
+        if objective(x1.exp()) > objective(x2.exp()) {
+            hi = x2;
+        } else {
+            lo = x1;
+        }
+    }
+    let k = ((lo + hi) / 2.0).exp().exp();
+    (p / k, p * k)
+}
+
+/// A concentrated liquidity (Uniswap v3) share over a price range
+///
+/// Prices are quoted as units of the quote token per base token, e.g. USDC per ETH. Delta is measured in
+/// the base token and value in the quote token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClPosition {
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub liquidity: f64,
+    /// Amounts of the (base, quote) tokens deposited
+    pub reserves: (f64, f64),
+}
+
+impl ClPosition {
+    /// Creates a position from the tokens deposited into a range
+    ///
+    /// # Arguments
+    /// * `lower_price` - Lower tick range
+    /// * `upper_price` - Upper tick range
+    /// * `base_reserve` - Amount of the base token, e.g. ETH
+    /// * `quote_reserve` - Amount of the quote token, e.g. USDC
+    pub fn new(
+        lower_price: f64,
+        upper_price: f64,
+        base_reserve: f64,
+        quote_reserve: f64,
+    ) -> ClPosition {
+        ClPosition {
+            lower_price,
+            upper_price,
+            liquidity: liquidity_for_reserves(
+                lower_price,
+                upper_price,
+                base_reserve,
+                quote_reserve,
+            ),
+            reserves: (base_reserve, quote_reserve),
+        }
+    }
+
+    /// Whether the price lies within the range, where the position earns fees and has gamma
+    pub fn in_range(&self, p: f64) -> bool {
+        self.lower_price <= p && p <= self.upper_price
+    }
+
+    /// Delta in the base token, the full base amount below the range and zero above it
+    pub fn delta(&self, p: f64) -> f64 {
+        self.amounts(p).0
+    }
+
+    /// Gamma of the position, zero outside the range
+    pub fn gamma(&self, p: f64) -> f64 {
+        gamma_in_range(self.liquidity, p, self.lower_price, self.upper_price)
+    }
+
+    /// Value of the position in the quote token
+    pub fn value(&self, p: f64) -> f64 {
+        let (base, quote) = self.amounts(p);
+        base * p + quote
+    }
+
+    // Amounts of the (base, quote) tokens the share holds at a price
+    fn amounts(&self, p: f64) -> (f64, f64) {
+        cl_amounts_for_liquidity(self.liquidity, p, self.lower_price, self.upper_price)
+    }
+
+    /// Delta and gamma at a price, labelled with the base and quote token symbols
+    ///
+    /// # Arguments
+    /// * `p` - Current price in the quote token
+    /// * `base_token` - Symbol of the base token, e.g. ETH
+    /// * `quote_token` - Symbol of the quote token, e.g. USDC
+    pub fn greeks(&self, p: f64, base_token: &str, quote_token: &str) -> ClGreeks {
+        ClGreeks {
+            delta: self.delta(p),
+            gamma: self.gamma(p),
+            base_token: base_token.to_string(),
+            quote_token: quote_token.to_string(),
+        }
+    }
+}
+
+/// Calculates the impermanent loss of a position against holding the tokens it held at entry
+///
+/// The difference `value(p_now) - hold(p_now)` in the quote token, where the hold portfolio keeps the base and quote
+/// amounts of the share at `p_entry`. It is zero at entry and negative for any move away from it.
+/// # Arguments
+/// * `position` - The liquidity position
+/// * `p_entry` - Price when the position was opened
+/// * `p_now` - Current price
+/// # Return
+/// * impermanent loss in units of the quote token
+pub fn concentrated_impermanent_loss(position: &ClPosition, p_entry: f64, p_now: f64) -> f64 {
+    let (p_a, p_b) = (position.lower_price, position.upper_price);
+    let (entry_base, entry_quote) = cl_amounts_for_liquidity(position.liquidity, p_entry, p_a, p_b);
+    let (base, quote) = cl_amounts_for_liquidity(position.liquidity, p_now, p_a, p_b);
+    (base - entry_base) * p_now + quote - entry_quote
+}
+
+/// Calculates the sensitivity of impermanent loss to the current price
+///
+/// The first derivative of `concentrated_impermanent_loss`, the position delta less the base token held at entry.
+/// Shorting this amount of the base token hedges the loss against small moves.
+/// # Arguments
+/// * `position` - The liquidity position
+/// * `p_entry` - Price when the position was opened
+/// * `p_now` - Current price
+/// # Return
+/// * impermanent loss delta in the base token
+pub fn il_delta(position: &ClPosition, p_entry: f64, p_now: f64) -> f64 {
+    position.delta(p_now) - position.delta(p_entry)
+}
+
+/// Calculates the convexity of impermanent loss in the current price
+///
+/// The second derivative of `concentrated_impermanent_loss`. The hold portfolio is linear in price, so this is the
+/// curvature of the position and does not depend on the entry price. The share is short gamma, so this is the
+/// negative of `concentrated_gamma`, which reports the magnitude.
+/// # Arguments
+/// * `position` - The liquidity position
+/// * `p_now` - Current price
+/// # Return
+/// * impermanent loss gamma
+pub fn il_gamma(position: &ClPosition, p_now: f64) -> f64 {
+    -position.gamma(p_now)
+}
+
+/// Delta and gamma of a concentrated liquidity position labelled with the tokens they are measured in
+///
+/// Delta is an amount of the base token, and gamma is the change in that amount for a one unit move of the price
+/// in the quote token. Printing it names both, e.g. `delta: 1.451752 ETH, gamma: 0.002435 ETH per USDC`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClGreeks {
+    pub delta: f64,
+    pub gamma: f64,
+    /// Symbol of the base token, the token delta is measured in
+    pub base_token: String,
+    /// Symbol of the quote token, the numeraire prices are quoted in
+    pub quote_token: String,
+}
+
+impl fmt::Display for ClGreeks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "delta: {:.6} {}, gamma: {:.6} {} per {}",
+            self.delta, self.base_token, self.gamma, self.base_token, self.quote_token
+        )
+    }
+}
+
+/// Calculates the greeks of a concentrated liquidity position at each price on a grid
+///
+/// Only delta and gamma are populated, with gamma zero outside of the range.
+/// # Arguments
+/// * `position` - The liquidity position
+/// * `prices` - Prices to evaluate
+/// # Return
+/// * greeks at each price
+pub fn cl_greeks_profile(position: &ClPosition, prices: &[f64]) -> Vec<Greeks> {
+    prices
+        .iter()
+        .map(|&p| Greeks {
+            delta: position.delta(p),
+            gamma: position.gamma(p),
+            ..Greeks::default()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::impermanent_loss_moments;
     use greeks::*;
 
-    // a token reserves
-    const R_A: f32 = 6779.0;
-    // b token reserves
-    const R_B: f32 = 1.448;
+    // base token (ETH) reserves
+    const BASE_RESERVE: f32 = 1.448;
+    // quote token (USDC) reserves
+    const QUOTE_RESERVE: f32 = 6779.0;
     // lower range
     const P_A: f32 = 3747.0;
     // upper range
@@ -91,23 +549,35 @@ mod tests {
 
     #[test]
     fn test_virtual_liquidity() {
-        let virtual_liquidity = virtual_liquidity(
-            P_A, P_B, R_B,
-            R_A, // note: its tough to figure out which token is what you are pricing your greeks in, sometimes you might need to switch them around
-        );
+        let virtual_liquidity = virtual_liquidity(P_A, P_B, BASE_RESERVE, QUOTE_RESERVE);
 
         let abs = (virtual_liquidity - E_RV).abs();
         assert!(abs < 0.1);
     }
 
+    #[test]
+    fn test_virtual_liquidity_token_order() {
+        // 1.448 ETH and 6779 USDC in a 3747 - 5024 USDC per ETH range at 4360.61 USDC per ETH
+        let (eth, usdc) = (1.448, 6779.0);
+        let liquidity = virtual_liquidity(P_A, P_B, eth, usdc);
+        assert!((liquidity - E_RV).abs() < 0.1);
+
+        // the share gives back the same ETH and USDC it was minted from
+        let (base, quote) =
+            cl_amounts_for_liquidity(liquidity as f64, P as f64, P_A as f64, P_B as f64);
+        assert!((base - eth as f64).abs() / (eth as f64) < 0.005);
+        assert!((quote - usdc as f64).abs() / (usdc as f64) < 0.005);
+
+        // swapping the tokens describes a different share entirely
+        let swapped = virtual_liquidity(P_A, P_B, usdc, eth);
+        assert!((swapped - liquidity).abs() / liquidity > 0.5);
+    }
+
     #[test]
     fn test_delta() {
-        let virtual_liquidity = virtual_liquidity(
-            P_A, P_B, R_B,
-            R_A, // note: its tough to figure out which token is what you are pricing your greeks in, sometimes you might need to switch them around
-        );
+        let virtual_liquidity = virtual_liquidity(P_A, P_B, BASE_RESERVE, QUOTE_RESERVE);
 
-        let delta = concentrated_delta(virtual_liquidity, P, P_B);
+        let delta = concentrated_delta(virtual_liquidity, P, P_A, P_B);
 
         let abs = (delta - E_DELTA).abs();
         assert!(abs < 0.1);
@@ -115,14 +585,267 @@ mod tests {
 
     #[test]
     fn test_gamma() {
-        let virtual_liquidity = virtual_liquidity(
-            P_A, P_B, R_B,
-            R_A, // note: its tough to figure out which token is what you are pricing your greeks in, sometimes you might need to switch them around
-        );
+        let virtual_liquidity = virtual_liquidity(P_A, P_B, BASE_RESERVE, QUOTE_RESERVE);
 
-        let gamma = concentrated_gamma(virtual_liquidity, P);
+        let gamma = concentrated_gamma(virtual_liquidity, P, P_A, P_B);
 
         let abs = (gamma - E_GAMMA).abs();
         assert!(abs < 0.1);
     }
+
+    #[test]
+    fn test_concentrated_greeks_across_range() {
+        let l = virtual_liquidity(P_A, P_B, BASE_RESERVE, QUOTE_RESERVE);
+        let (below, above) = (0.9 * P_A, 1.1 * P_B);
+
+        // below the range the share is all base token
+        let max_delta = l * (1.0 / P_A.sqrt() - 1.0 / P_B.sqrt());
+        assert_eq!(concentrated_delta(l, below, P_A, P_B), max_delta);
+        assert_eq!(concentrated_gamma(l, below, P_A, P_B), 0.0);
+
+        let delta = concentrated_delta(l, P, P_A, P_B);
+        assert!(0.0 < delta && delta < max_delta);
+        assert!(concentrated_gamma(l, P, P_A, P_B) > 0.0);
+
+        // above the range it is all quote token
+        assert_eq!(concentrated_delta(l, above, P_A, P_B), 0.0);
+        assert_eq!(concentrated_gamma(l, above, P_A, P_B), 0.0);
+    }
+
+    #[test]
+    fn test_virtual_liquidity_single_sided() {
+        // deposited at the lower bound, the share holds only the base token
+        let at_lower = virtual_liquidity(P_A, P_B, BASE_RESERVE, 0.0);
+        let base = at_lower * (1.0 / P_A.sqrt() - 1.0 / P_B.sqrt());
+        assert!((base - BASE_RESERVE).abs() / BASE_RESERVE < 1e-5);
+
+        // deposited at the upper bound, the share holds only the quote token
+        let at_upper = virtual_liquidity(P_A, P_B, 0.0, QUOTE_RESERVE);
+        let quote = at_upper * (P_B.sqrt() - P_A.sqrt());
+        assert!((quote - QUOTE_RESERVE).abs() / QUOTE_RESERVE < 1e-5);
+
+        // a vanishing second token converges to the single-sided liquidity
+        let nearly = virtual_liquidity(P_A, P_B, BASE_RESERVE, 1e-3);
+        assert!((nearly - at_lower).abs() / at_lower < 1e-4);
+    }
+
+    #[test]
+    fn test_cl_amounts_for_liquidity() {
+        let (base, quote) = cl_amounts_for_liquidity(E_RV as f64, P as f64, P_A as f64, P_B as f64);
+        assert!((base - BASE_RESERVE as f64).abs() / (BASE_RESERVE as f64) < 0.005);
+        assert!((quote - QUOTE_RESERVE as f64).abs() / (QUOTE_RESERVE as f64) < 0.005);
+
+        let liquidity = virtual_liquidity(P_A, P_B, base as f32, quote as f32);
+        assert!((liquidity - E_RV).abs() / E_RV < 1e-5);
+
+        // single token outside of the range
+        let (_, below_quote) =
+            cl_amounts_for_liquidity(E_RV as f64, 0.5 * P_A as f64, P_A as f64, P_B as f64);
+        let (above_base, _) =
+            cl_amounts_for_liquidity(E_RV as f64, 2.0 * P_B as f64, P_A as f64, P_B as f64);
+        assert_eq!(below_quote, 0.0);
+        assert_eq!(above_base, 0.0);
+    }
+
+    #[test]
+    fn test_v2_to_v3_equivalent() {
+        let (base, quote) = (BASE_RESERVE as f64, BASE_RESERVE as f64 * P as f64);
+        let p = P as f64;
+        let v2_liquidity = (base * quote).sqrt();
+
+        // a very wide range buys almost exactly the v2 liquidity and shares its delta
+        let (wide_a, wide_b) = (p / 1e6, p * 1e6);
+        let wide = v2_to_v3_equivalent(base, quote, wide_a, wide_b);
+        assert!((wide - v2_liquidity).abs() / v2_liquidity < 0.005);
+        let v2_delta = v2_liquidity / p.sqrt();
+        let v3_delta = concentrated_delta(wide as f32, P, wide_a as f32, wide_b as f32) as f64;
+        assert!((v3_delta - v2_delta).abs() / v2_delta < 0.005);
+        assert!(v3_full_range_check(base, quote, wide_a, wide_b, 0.005));
+
+        // the fixture range concentrates the same capital into several times the liquidity
+        let narrow = v2_to_v3_equivalent(base, quote, P_A as f64, P_B as f64);
+        assert!(narrow > 5.0 * v2_liquidity);
+        // and depositing the tokens it needs at the v2 price mints exactly that liquidity for the v2 capital
+        let (narrow_base, narrow_quote) =
+            cl_amounts_for_liquidity(narrow, p, P_A as f64, P_B as f64);
+        let minted = ClPosition::new(P_A as f64, P_B as f64, narrow_base, narrow_quote);
+        assert!((minted.liquidity - narrow).abs() / narrow < 1e-12);
+        assert!((minted.value(p) - (base * p + quote)).abs() / (base * p + quote) < 1e-12);
+        assert!(!v3_full_range_check(
+            base, quote, P_A as f64, P_B as f64, 0.005
+        ));
+    }
+
+    #[test]
+    fn test_optimal_cl_range() {
+        let (p, t, fee_apr) = (P as f64, 30.0 / 365.0, 0.2);
+        let (calm_a, calm_b) = optimal_cl_range(p, 0.5, t, fee_apr, 0.0);
+        assert!(calm_a < p && p < calm_b);
+        // symmetric in log price
+        assert!((p / calm_a - calm_b / p).abs() < 1e-6);
+        // an interior optimum, neither a sliver nor close to full range
+        assert!(1.05 < calm_b / p && calm_b / p < 2.0);
+
+        // higher volatility widens the range
+        let (wild_a, wild_b) = optimal_cl_range(p, 0.8, t, fee_apr, 0.0);
+        assert!(wild_a < calm_a && calm_b < wild_b);
+
+        // so does weighting the impermanent loss more heavily
+        let (averse_a, averse_b) = optimal_cl_range(p, 0.5, t, fee_apr, 10.0);
+        assert!(averse_a < calm_a && calm_b < averse_b);
+    }
+
+    #[test]
+    fn test_cl_breakeven_vol() {
+        let (p, t) = (P as f64, 7.0 / 365.0);
+        let (p_a, p_b) = (P_A as f64, P_B as f64);
+        let low = cl_breakeven_vol(p, p_a, p_b, 0.1, t);
+        let high = cl_breakeven_vol(p, p_a, p_b, 0.5, t);
+        assert!(0.0 < low && low < high);
+
+        // over a short horizon the loss is the gamma rent on the share's value
+        let value = concentrated_value(1.0, P, P_A, P_B) as f64;
+        let gamma = concentrated_gamma(1.0, P, P_A, P_B) as f64;
+        let gamma_rent = (2.0 * 0.5 * value / (gamma * p * p)).sqrt();
+        assert!((high - gamma_rent).abs() / gamma_rent < 0.02);
+
+        // a wider range holds less gamma per unit of value, so it needs more volatility to lose its fees
+        let wide = cl_breakeven_vol(p, 0.5 * p_a, 2.0 * p_b, 0.5, t);
+        assert!(wide > high);
+
+        // the accrued rent is the expected shortfall against holding, so at the breakeven the fees also cover the
+        // impermanent loss integrated directly over the terminal price
+        let month = 30.0 / 365.0;
+        let sigma = cl_breakeven_vol(p, p_a, p_b, 0.5, month);
+        let fees = 0.5 * month * value;
+        let loss = impermanent_loss_moments(p, p_a, p_b, sigma, month).0;
+        assert!((loss - fees).abs() / fees < 0.01);
+    }
+
+    #[test]
+    fn test_cl_position_in_range() {
+        let position = ClPosition::new(
+            P_A as f64,
+            P_B as f64,
+            BASE_RESERVE as f64,
+            QUOTE_RESERVE as f64,
+        );
+        assert!((position.liquidity - E_RV as f64).abs() < 0.1);
+        assert!(position.in_range(P as f64));
+        assert!((position.delta(P as f64) - E_DELTA as f64).abs() < 0.001);
+        assert!((position.gamma(P as f64) - E_GAMMA as f64).abs() < 0.00001);
+        // the deposit is worth its reserves at the current price
+        let deposit = BASE_RESERVE as f64 * P as f64 + QUOTE_RESERVE as f64;
+        assert!((position.value(P as f64) - deposit).abs() / deposit < 0.005);
+
+        // minting the amounts a liquidity needs gives that liquidity back without losing precision to f32
+        let (p, p_a, p_b) = (4360.61, 3747.0, 5024.0);
+        let (base, quote) = cl_amounts_for_liquidity(1402.404637954, p, p_a, p_b);
+        let minted = ClPosition::new(p_a, p_b, base, quote);
+        assert!((minted.liquidity - 1402.404637954).abs() < 1e-9);
+        assert!((minted.delta(p) - base).abs() < 1e-12);
+        assert!((minted.value(p) - (base * p + quote)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cl_position_out_of_range() {
+        let position = ClPosition::new(
+            P_A as f64,
+            P_B as f64,
+            BASE_RESERVE as f64,
+            QUOTE_RESERVE as f64,
+        );
+        let (below, above) = (0.9 * P_A as f64, 1.1 * P_B as f64);
+        assert!(!position.in_range(below));
+        assert!(!position.in_range(above));
+        assert_eq!(position.gamma(below), 0.0);
+        assert_eq!(position.gamma(above), 0.0);
+        // all base token below the range, all quote token above it
+        assert_eq!(position.delta(below), position.delta(P_A as f64));
+        assert_eq!(position.delta(above), 0.0);
+        assert!((position.value(below) - position.delta(below) * below).abs() < 0.01);
+        assert!((position.value(above) - position.value(P_B as f64)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_concentrated_impermanent_loss() {
+        let position = ClPosition::new(
+            P_A as f64,
+            P_B as f64,
+            BASE_RESERVE as f64,
+            QUOTE_RESERVE as f64,
+        );
+        let entry = P as f64;
+        assert!(concentrated_impermanent_loss(&position, entry, entry).abs() < 1e-9);
+        for &p in &[0.8 * entry, 0.95 * entry, 1.05 * entry, 1.2 * entry] {
+            assert!(concentrated_impermanent_loss(&position, entry, p) < 0.0);
+        }
+    }
+
+    #[test]
+    fn test_il_delta_and_gamma() {
+        let position = ClPosition::new(
+            P_A as f64,
+            P_B as f64,
+            BASE_RESERVE as f64,
+            QUOTE_RESERVE as f64,
+        );
+        let entry = P as f64;
+        assert_eq!(il_delta(&position, entry, entry), 0.0);
+
+        let il = |p: f64| concentrated_impermanent_loss(&position, entry, p);
+        for &move_pct in &[-0.1, -0.03, 0.03, 0.1] {
+            let p = entry * (1.0 + move_pct);
+            let h = 10.0;
+            let fd_delta = (il(p + h) - il(p - h)) / (2.0 * h);
+            let fd_gamma = (il(p + h) - 2.0 * il(p) + il(p - h)) / h.powf(2.0);
+            assert!((il_delta(&position, entry, p) - fd_delta).abs() < 1e-3);
+            assert!((il_gamma(&position, p) - fd_gamma).abs() < 1e-4);
+            // the loss deepens in the direction of the move
+            assert!(il_gamma(&position, p) < 0.0);
+            assert_eq!(il_delta(&position, entry, p) < 0.0, move_pct > 0.0);
+        }
+
+        // outside the range the loss is linear in price
+        assert_eq!(il_gamma(&position, 1.2 * P_B as f64), 0.0);
+    }
+
+    #[test]
+    fn test_cl_greeks_display() {
+        let position = ClPosition::new(
+            P_A as f64,
+            P_B as f64,
+            BASE_RESERVE as f64,
+            QUOTE_RESERVE as f64,
+        );
+        let greeks = position.greeks(P as f64, "ETH", "USDC");
+        assert_eq!(greeks.delta, position.delta(P as f64));
+        assert_eq!(greeks.gamma, position.gamma(P as f64));
+
+        let formatted = greeks.to_string();
+        assert!(formatted.starts_with("delta: 1.45"));
+        assert!(formatted.contains(" ETH, gamma: 0.0024"));
+        assert!(formatted.ends_with(" ETH per USDC"));
+    }
+
+    #[test]
+    fn test_cl_greeks_profile() {
+        let position = ClPosition::new(
+            P_A as f64,
+            P_B as f64,
+            BASE_RESERVE as f64,
+            QUOTE_RESERVE as f64,
+        );
+        let prices: Vec<f64> = (0..40).map(|i| 3000.0 + 70.0 * i as f64).collect();
+        let profile = cl_greeks_profile(&position, &prices);
+        assert_eq!(profile.len(), prices.len());
+        for (p, greeks) in prices.iter().zip(profile.iter()) {
+            if position.in_range(*p) {
+                assert!(greeks.gamma > 0.0);
+            } else {
+                assert_eq!(greeks.gamma, 0.0);
+            }
+            assert_eq!(greeks.delta, position.delta(*p));
+        }
+    }
 }
@@ -2,7 +2,8 @@
 use std::f64::consts::E;
 
 use common::*;
-use stats::cnd;
+use greeks::{gamma_d1, Greeks};
+use stats::{cnd, inv_cnd, pdf};
 
 /// Calculates the delta of a call option.
 ///
@@ -40,6 +41,128 @@ pub fn delta_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
     return e * (cnd - 1.0);
 }
 
+/// Calculates the delta of a call or put option, dispatching to `delta_call` or `delta_put`
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn delta(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    match kind {
+        OptionType::Call => delta_call(s0, x, t, r, q, sigma),
+        OptionType::Put => delta_put(s0, x, t, r, q, sigma),
+    }
+}
+
+/// Calculates the delta of an option on a leveraged or rebasing token with respect to the reference asset
+///
+/// A token with leverage factor `k` moves by `k * dS` for a move `dS` in the reference asset, so its option's delta
+/// against the reference is `k` times the delta against the token. The greeks are evaluated at the token's own
+/// price and volatility.
+///
+/// # Arguments
+/// * `k` - leverage factor of the token, e.g. 3 for a 3x token
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The price of the token
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility of the token
+#[allow(clippy::too_many_arguments)]
+pub fn delta_levered(
+    k: f64,
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> f64 {
+    k * delta(kind, s0, x, t, r, q, sigma)
+}
+
+/// Calculates the minimum variance delta, the Black-Scholes delta adjusted for the volatility moving with spot
+///
+/// Under a sticky skew the implied volatility moves by `skew_slope` per unit move in the underlying, so the hedge
+/// ratio picks up the vega times that slope. A downward sloping skew lowers the delta of calls and puts alike.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `skew_slope` - change in volatility per unit change in the underlying price, dSigma/dSpot
+#[allow(clippy::too_many_arguments)]
+pub fn min_variance_delta(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    skew_slope: f64,
+) -> f64 {
+    // vega is quoted per volatility point
+    delta(kind, s0, x, t, r, q, sigma) + 100.0 * vega(s0, x, t, r, q, sigma) * skew_slope
+}
+
+/// Calculates the strike at which a call has the requested delta
+///
+/// Inverts `delta = e^(-qt) * N(d1)` for `d1` and solves `d1` for the strike.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `delta` - The target delta, between 0 and `e^(-qt)`
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn strike_for_delta_call(s0: f64, delta: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = inv_cnd(delta * E.powf(q * t));
+    s0 * E.powf((r - q + sigma.powf(2.0) / 2.0) * t - d1 * sigma * t.sqrt())
+}
+
+/// Calculates the strike at which a put has the requested delta
+///
+/// Inverts `delta = e^(-qt) * (N(d1) - 1)` for `d1` and solves `d1` for the strike.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `delta` - The target delta, between `-e^(-qt)` and 0
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn strike_for_delta_put(s0: f64, delta: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = inv_cnd(1.0 + delta * E.powf(q * t));
+    s0 * E.powf((r - q + sigma.powf(2.0) / 2.0) * t - d1 * sigma * t.sqrt())
+}
+
+/// Calculates the delta-neutral straddle strike, where the call and put deltas cancel
+///
+/// `delta_call + delta_put = e^(-qt) * (2 * N(d1) - 1)` which is zero exactly when `d1 = 0`,
+/// giving the strike `F * e^(sigma^2 * t / 2)` above the forward `F`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn delta_neutral_strike(s0: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    forward_price(s0, t, r, q) * E.powf(sigma.powf(2.0) * t / 2.0)
+}
+
 /// Calculates the lambda of a call option, also known as Omega
 ///
 /// Omega is the percentage of change in an option's value with respect to the percentage change in the underlying price.
@@ -110,6 +233,55 @@ pub fn rho_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
     return -(1.0 / 100.0) * x * t * E.powf(-r * t) * neg_d2_cnd;
 }
 
+/// Calculates the Rho of a call or put option, dispatching to `rho_call` or `rho_put`
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn rho(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    match kind {
+        OptionType::Call => rho_call(s0, x, t, r, q, sigma),
+        OptionType::Put => rho_put(s0, x, t, r, q, sigma),
+    }
+}
+
+/// Calculates the Epsilon of a call option, also known as Psi or dividend rho
+///
+/// Epsilon measures the sensitivity to the dividend yield. Like rho it is scaled to a percentage point move in the yield.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn epsilon_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1_cnd = cnd(d1(s0, x, t, r, q, sigma));
+    -(1.0 / 100.0) * s0 * t * E.powf(-q * t) * d1_cnd
+}
+
+/// Calculates the Epsilon of a put option, also known as Psi or dividend rho
+///
+/// Epsilon measures the sensitivity to the dividend yield. Like rho it is scaled to a percentage point move in the yield.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn epsilon_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let neg_d1_cnd = cnd(-d1(s0, x, t, r, q, sigma));
+    (1.0 / 100.0) * s0 * t * E.powf(-q * t) * neg_d1_cnd
+}
+
 /// Calculates the Theta of a call option
 ///
 /// Theta measures the sensitivity of the value of the derivative to the passage of time.
@@ -143,10 +315,186 @@ pub fn theta_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64, days_per_y
     return (1.0 / days_per_year) * (arg1 + arg2 - arg3);
 }
 
+/// Calculates the Theta of a call or put option, dispatching to `theta_call` or `theta_put`
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+#[allow(clippy::too_many_arguments)]
+pub fn theta(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> f64 {
+    match kind {
+        OptionType::Call => theta_call(s0, x, t, r, q, sigma, days_per_year),
+        OptionType::Put => theta_put(s0, x, t, r, q, sigma, days_per_year),
+    }
+}
+
+/// Calculates the Theta of a call option under a generalized cost of carry
+///
+/// Follows Haug's `b` parameterization where the carry replaces the dividend yield, so that
+/// `b = r - q` recovers `theta_call`, `b = 0` gives Black-76 on a futures price and `b = r` gives
+/// the non-dividend Black-Scholes theta.
+///
+/// # Arguments
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn theta_call_carry(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    b: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> f64 {
+    theta_call(s0, x, t, r, r - b, sigma, days_per_year)
+}
+
+/// Calculates the Theta of a put option under a generalized cost of carry
+///
+/// See `theta_call_carry` for the mapping between `b` and the individual pricing models.
+///
+/// # Arguments
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn theta_put_carry(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    b: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> f64 {
+    theta_put(s0, x, t, r, r - b, sigma, days_per_year)
+}
+
+/// Calculates the Theta of an option over one calendar day when weekends carry less variance than trading days
+///
+/// Keeps the total variance `sigma^2 * t` to expiry but releases it unevenly, so a trading day decays
+/// the volatility part of theta by `1 / business_time_fraction(1, w)` and a weekend day by `w` times that.
+/// Over a full week the decay matches the naive calendar theta. Carry and discounting still accrue daily.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+/// * `weekend_vol_weight` - variance of a weekend day relative to a trading day
+/// * `trading_day` - whether the day being decayed is a trading day
+#[allow(clippy::too_many_arguments)]
+pub fn theta_business(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+    weekend_vol_weight: f64,
+    trading_day: bool,
+) -> f64 {
+    let naive = theta(kind, s0, x, t, r, q, sigma, days_per_year);
+    let day_weight = if trading_day { 1.0 } else { weekend_vol_weight };
+    let vol_weight = day_weight / business_time_fraction(1.0, weekend_vol_weight);
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let vol_decay = theta_arg_1(s0, t, q, sigma, d1) / days_per_year;
+    naive + (vol_weight - 1.0) * vol_decay
+}
+
+/// Calculates the Theta of an option per unit of delta-equivalent exposure to the underlying
+///
+/// Theta divided by the dollar delta `delta * s0`, the daily decay paid for each dollar of directional exposure,
+/// which compares the carry of options at different strikes against holding the underlying. The sign follows
+/// the delta, so it is negative for calls and usually positive for puts.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+#[allow(clippy::too_many_arguments)]
+pub fn theta_carry_ratio(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> f64 {
+    let theta = theta(kind, s0, x, t, r, q, sigma, days_per_year);
+    let delta = delta(kind, s0, x, t, r, q, sigma);
+    theta / (delta * s0)
+}
+
+/// Calculates the Theta of an option delta-hedged with the underlying, including the carry on the hedge
+///
+/// A long option is hedged by shorting `delta` units of the underlying, which earns the rate on the sale proceeds
+/// and pays the dividends, adding `delta * s0 * (r - q)` a year to the option's theta. By the Black-Scholes
+/// equation the sum is the financing of the premium less the gamma decay, `r * V - sigma^2 * s0^2 * gamma / 2`.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+#[allow(clippy::too_many_arguments)]
+pub fn hedged_theta(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> f64 {
+    let theta = theta(kind, s0, x, t, r, q, sigma, days_per_year);
+    let delta = delta(kind, s0, x, t, r, q, sigma);
+    theta + delta * s0 * (r - q) / days_per_year
+}
+
 fn theta_arg_1(s0: f64, t: f64, q: f64, sigma: f64, d1: f64) -> f64 {
-    return -(((s0 * sigma * E.powf(-q * t)) / (2.0 * t.sqrt()))
-        * one_over_sqrt_pi()
-        * E.powf((-d1.powf(2.0)) / 2.0));
+    return -(((s0 * sigma * E.powf(-q * t)) / (2.0 * t.sqrt())) * pdf(d1));
 }
 
 fn theta_arg_2(x: f64, t: f64, r: f64, d2: f64) -> f64 {
@@ -157,6 +505,51 @@ fn theta_arg_3(s0: f64, t: f64, q: f64, d1: f64) -> f64 {
     return q * s0 * E.powf(-q * t) * cnd(d1);
 }
 
+/// Calculates the Charm of a call option, also known as delta decay
+///
+/// Charm measures the change in delta over the passage of one calendar day.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn charm_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64, days_per_year: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let arg1 = q * E.powf(-q * t) * cnd(d1);
+    let arg2 = charm_arg(t, r, q, sigma, d1);
+    (1.0 / days_per_year) * (arg1 - arg2)
+}
+
+/// Calculates the Charm of a put option, also known as delta decay
+///
+/// Charm measures the change in delta over the passage of one calendar day.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn charm_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64, days_per_year: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let arg1 = q * E.powf(-q * t) * cnd(-d1); // the put takes N(-d1) where the call takes N(d1)
+    let arg2 = charm_arg(t, r, q, sigma, d1);
+    (1.0 / days_per_year) * (-arg1 - arg2)
+}
+
+fn charm_arg(t: f64, r: f64, q: f64, sigma: f64, d1: f64) -> f64 {
+    let d2 = d2_d1(t, sigma, d1);
+    let pdf = pdf(d1);
+    E.powf(-q * t) * pdf * (2.0 * (r - q) * t - d2 * sigma * t.sqrt())
+        / (2.0 * t * sigma * t.sqrt())
+}
+
 /// Calculates the Vega of a given option
 ///
 /// Vega measures the sensitivity to volatility. Vega is the derivative of the option value with respect to the volatility of the underlying asset.
@@ -175,76 +568,169 @@ pub fn vega(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
 
 pub fn vega_d1(s0: f64, t: f64, q: f64, d1: f64) -> f64 {
     let mult1 = (1.0 / 100.0) * s0 * E.powf(-(q * t)) * t.sqrt();
-    let mult2 = one_over_sqrt_pi();
-    let mult3 = E.powf(-d1.powf(2.0) / 2.0);
-    return mult1 * mult2 * mult3;
+    return mult1 * pdf(d1);
+}
+
+/// Calculates delta, gamma, theta, vega and rho of a call option in one pass
+///
+/// Computes `d1` and `d2` once and shares them between the greeks, which otherwise each recompute them.
+/// Matches the standalone functions field for field.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn greeks_call(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> Greeks {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    let arg1 = theta_arg_1(s0, t, q, sigma, d1);
+    let arg2 = theta_arg_2(x, t, r, d2);
+    let arg3 = theta_arg_3(s0, t, q, d1);
+    Greeks {
+        delta: E.powf(-(q * t)) * cnd(d1),
+        gamma: gamma_d1(s0, t, q, sigma, d1),
+        theta: (1.0 / days_per_year) * (arg1 - arg2 + arg3),
+        vega: vega_d1(s0, t, q, d1),
+        rho: (1.0 / 100.0) * x * t * E.powf(-r * t) * cnd(d2),
+    }
+}
+
+/// Calculates delta, gamma, theta, vega and rho of a put option in one pass
+///
+/// Computes `d1` and `d2` once and shares them between the greeks, which otherwise each recompute them.
+/// Matches the standalone functions field for field.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn greeks_put(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> Greeks {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    let arg1 = theta_arg_1(s0, t, q, sigma, d1);
+    let arg2 = theta_arg_2(x, t, r, -d2);
+    let arg3 = theta_arg_3(s0, t, q, -d1);
+    Greeks {
+        delta: E.powf(-(q * t)) * (cnd(d1) - 1.0),
+        gamma: gamma_d1(s0, t, q, sigma, d1),
+        theta: (1.0 / days_per_year) * (arg1 + arg2 - arg3),
+        vega: vega_d1(s0, t, q, d1),
+        rho: -(1.0 / 100.0) * x * t * E.powf(-r * t) * cnd(-d2),
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use common::{forward_price, OptionType};
     use greeks::*;
+    use price::{euro_call, euro_put};
+    use stats::cnd;
+    use test_fixtures::SAMPLE_PARAMS;
     use value::*;
 
-    const UNDERLYING: f64 = 64.68;
-    const STRIKE: f64 = 65.00;
-    const VOL: f64 = 0.5051;
-    const INTEREST_RATE: f64 = 0.0150;
-    const DIV_YIELD: f64 = 0.0210;
-    const DAYS_PER_YEAR: f64 = 365.0;
-    const TIME_TO_EXPIRY: f64 = 23.0 / DAYS_PER_YEAR;
-
     const E_CALL_DELTA: f64 = 0.5079;
     const E_PUT_DELTA: f64 = -0.4908;
     const E_LAMBDA_PUT: f64 = -3.0759;
     const E_LAMBDA_CALL: f64 = 3.3936;
     const E_RHO_CALL: f64 = 0.0187;
     const E_RHO_PUT: f64 = -0.0222;
+    const E_EPSILON_CALL: f64 = -0.0207;
+    const E_EPSILON_PUT: f64 = 0.0200;
     const E_THETA_CALL: f64 = -0.0703;
     const E_THETA_PUT: f64 = -0.0714;
     const E_VEGA: f64 = 0.0647;
+    // per year, the per day values are these over the sample days_per_year
+    const E_CHARM_CALL: f64 = -0.3033;
+    const E_CHARM_PUT: f64 = -0.3243;
 
     #[test]
     fn test_delta_call() {
-        let call_delta = delta_call(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let call_delta = delta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (call_delta - E_CALL_DELTA).abs();
         assert!(abs < 0.001);
     }
 
     #[test]
     fn test_delta_put() {
-        let put_delta = delta_put(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let put_delta = delta_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (put_delta - E_PUT_DELTA).abs();
         assert!(abs < 0.001);
     }
 
+    #[test]
+    fn test_option_type_dispatch() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let (call, put) = (OptionType::Call, OptionType::Put);
+        assert_eq!(delta(call, s, x, t, r, q, v), delta_call(s, x, t, r, q, v));
+        assert_eq!(delta(put, s, x, t, r, q, v), delta_put(s, x, t, r, q, v));
+        assert_eq!(rho(call, s, x, t, r, q, v), rho_call(s, x, t, r, q, v));
+        assert_eq!(rho(put, s, x, t, r, q, v), rho_put(s, x, t, r, q, v));
+        assert_eq!(
+            theta(call, s, x, t, r, q, v, p.days_per_year),
+            theta_call(s, x, t, r, q, v, p.days_per_year)
+        );
+        assert_eq!(
+            theta(put, s, x, t, r, q, v, p.days_per_year),
+            theta_put(s, x, t, r, q, v, p.days_per_year)
+        );
+    }
+
+    #[test]
+    fn test_min_variance_delta() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        // volatility up 0.2 points for each dollar the underlying falls
+        let slope = -0.002;
+        let call = min_variance_delta(OptionType::Call, s, x, t, r, q, v, slope);
+        let put = min_variance_delta(OptionType::Put, s, x, t, r, q, v, slope);
+        assert!(call < delta_call(s, x, t, r, q, v));
+        assert!(put < delta_put(s, x, t, r, q, v));
+        assert_eq!(
+            min_variance_delta(OptionType::Call, s, x, t, r, q, v, 0.0),
+            delta_call(s, x, t, r, q, v)
+        );
+
+        // matches the slope of the price when the volatility follows the skew
+        let h = 0.1;
+        let price = |spot: f64| euro_call(spot, x, t, r, q, v + slope * (spot - s));
+        let fd = (price(s + h) - price(s - h)) / (2.0 * h);
+        assert!(((call - fd) / fd).abs() < 1e-3);
+    }
+
     #[test]
     fn test_lambda_put() {
+        let p = SAMPLE_PARAMS;
         // Abitrary change in underlying at expiry
-        let price = put_at_expiry(UNDERLYING - 10.0, STRIKE);
-        let lambda = lambda_put(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-            price,
-        );
+        let price = put_at_expiry(p.s0 - 10.0, p.x);
+        let lambda = lambda_put(p.s0, p.x, p.t, p.r, p.q, p.sigma, price);
         println!("{}", lambda);
         let abs = (lambda - E_LAMBDA_PUT).abs();
         assert!(abs < 0.001);
@@ -252,90 +738,266 @@ mod tests {
 
     #[test]
     fn test_lambda_call() {
+        let p = SAMPLE_PARAMS;
         // abitrary change in underlying at expiry
-        let price = call_at_expiry(UNDERLYING + 10.0, STRIKE);
-        let lambda = lambda_call(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-            price,
-        );
+        let price = call_at_expiry(p.s0 + 10.0, p.x);
+        let lambda = lambda_call(p.s0, p.x, p.t, p.r, p.q, p.sigma, price);
         let abs = (lambda - E_LAMBDA_CALL).abs();
         assert!(abs < 0.001);
     }
 
     #[test]
     fn test_rho_call() {
-        let rho_call = rho_call(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let rho_call = rho_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (rho_call - E_RHO_CALL).abs();
         assert!(abs < 0.001);
     }
 
     #[test]
     fn test_rho_put() {
-        let rho_put = rho_put(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let rho_put = rho_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (rho_put - E_RHO_PUT).abs();
         assert!(abs < 0.001);
     }
 
+    #[test]
+    fn test_epsilon_call() {
+        let p = SAMPLE_PARAMS;
+        let epsilon_call = epsilon_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let abs = (epsilon_call - E_EPSILON_CALL).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_epsilon_put() {
+        let p = SAMPLE_PARAMS;
+        let epsilon_put = epsilon_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let abs = (epsilon_put - E_EPSILON_PUT).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_epsilon_finite_difference() {
+        let p = SAMPLE_PARAMS;
+        // epsilon is quoted per percentage point of yield, a hundredth of the derivative
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let h = 0.0001;
+        let call = (euro_call(s, x, t, r, q + h, v) - euro_call(s, x, t, r, q - h, v)) / (2.0 * h);
+        let put = (euro_put(s, x, t, r, q + h, v) - euro_put(s, x, t, r, q - h, v)) / (2.0 * h);
+        assert!((epsilon_call(s, x, t, r, q, v) - call / 100.0).abs() < 1e-9);
+        assert!((epsilon_put(s, x, t, r, q, v) - put / 100.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_theta_call() {
-        let theta_call = theta_call(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-            DAYS_PER_YEAR,
-        );
+        let p = SAMPLE_PARAMS;
+        let theta_call = theta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
         let abs = (theta_call - E_THETA_CALL).abs();
         assert!(abs < 0.001);
     }
 
     #[test]
     fn test_theta_put() {
-        let theta_put = theta_put(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-            DAYS_PER_YEAR,
-        );
+        let p = SAMPLE_PARAMS;
+        let theta_put = theta_put(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
         let abs = (theta_put - E_THETA_PUT).abs();
         assert!(abs < 0.001);
     }
 
     #[test]
     fn test_vega() {
-        let vega = vega(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let vega = vega(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (vega - E_VEGA).abs();
         assert!(abs < 0.001);
     }
+
+    #[test]
+    fn test_theta_call_carry_dividend_yield() {
+        let p = SAMPLE_PARAMS;
+        let theta_carry =
+            theta_call_carry(p.s0, p.x, p.t, p.r, p.r - p.q, p.sigma, p.days_per_year);
+        let abs = (theta_carry - E_THETA_CALL).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_theta_put_carry_dividend_yield() {
+        let p = SAMPLE_PARAMS;
+        let theta_carry = theta_put_carry(p.s0, p.x, p.t, p.r, p.r - p.q, p.sigma, p.days_per_year);
+        let abs = (theta_carry - E_THETA_PUT).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_theta_call_carry_no_dividend() {
+        let p = SAMPLE_PARAMS;
+        let theta_carry = theta_call_carry(p.s0, p.x, p.t, p.r, p.r, p.sigma, p.days_per_year);
+        let theta = theta_call(p.s0, p.x, p.t, p.r, 0.0, p.sigma, p.days_per_year);
+        let abs = (theta_carry - theta).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_theta_call_carry_black_76() {
+        let p = SAMPLE_PARAMS;
+        // Black-76 call on a futures price, differentiated numerically in time
+        let black_76 = |t: f64| {
+            let sd = p.sigma * t.sqrt();
+            let d1 = ((p.s0 / p.x).ln() + 0.5 * sd * sd) / sd;
+            let d2 = d1 - sd;
+            (-p.r * t).exp() * (p.s0 * cnd(d1) - p.x * cnd(d2))
+        };
+        let h = 1e-5;
+        let e_theta = -(black_76(p.t + h) - black_76(p.t - h)) / (2.0 * h) / p.days_per_year;
+
+        let theta_carry = theta_call_carry(p.s0, p.x, p.t, p.r, 0.0, p.sigma, p.days_per_year);
+        let abs = (theta_carry - e_theta).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_delta_neutral_strike() {
+        let p = SAMPLE_PARAMS;
+        let strike = delta_neutral_strike(p.s0, p.t, p.r, p.q, p.sigma);
+        let call_delta = delta_call(p.s0, strike, p.t, p.r, p.q, p.sigma);
+        let put_delta = delta_put(p.s0, strike, p.t, p.r, p.q, p.sigma);
+        assert!((call_delta + put_delta).abs() < 0.001);
+
+        let forward = forward_price(p.s0, p.t, p.r, p.q);
+        let variance_adjustment = (p.sigma * p.sigma * p.t / 2.0).exp();
+        assert!((strike - forward * variance_adjustment).abs() < 0.001);
+        assert!((strike - p.s0).abs() > 0.001);
+    }
+
+    #[test]
+    fn test_strike_for_delta() {
+        let p = SAMPLE_PARAMS;
+        for &delta in [0.1, 0.25, 0.5, 0.75].iter() {
+            let call_strike = strike_for_delta_call(p.s0, delta, p.t, p.r, p.q, p.sigma);
+            let call_delta = delta_call(p.s0, call_strike, p.t, p.r, p.q, p.sigma);
+            assert!((call_delta - delta).abs() < 1e-6);
+
+            let put_strike = strike_for_delta_put(p.s0, -delta, p.t, p.r, p.q, p.sigma);
+            let put_delta = delta_put(p.s0, put_strike, p.t, p.r, p.q, p.sigma);
+            assert!((put_delta + delta).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_theta_business() {
+        let p = SAMPLE_PARAMS;
+        let theta = |trading_day: bool| {
+            theta_business(
+                OptionType::Call,
+                p.s0,
+                p.x,
+                p.t,
+                p.r,
+                p.q,
+                p.sigma,
+                p.days_per_year,
+                0.2,
+                trading_day,
+            )
+        };
+        let naive = theta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
+        // weekends decay far less than the naive theta and trading days make up the difference
+        assert!(theta(false).abs() < 0.5 * naive.abs());
+        assert!(theta(true) < naive);
+        let week = (5.0 * theta(true) + 2.0 * theta(false)) / 7.0;
+        assert!((week - naive).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_theta_carry_ratio() {
+        let p = SAMPLE_PARAMS;
+        let (s, t, r, q, v) = (p.s0, p.t, p.r, p.q, p.sigma);
+        let ratio =
+            |kind: OptionType, x: f64| theta_carry_ratio(kind, s, x, t, r, q, v, p.days_per_year);
+
+        let call = ratio(OptionType::Call, p.x);
+        let expected = E_THETA_CALL / (E_CALL_DELTA * p.s0);
+        assert!((call - expected).abs() < 1e-4);
+        let put = ratio(OptionType::Put, p.x);
+        let expected = E_THETA_PUT / (E_PUT_DELTA * p.s0);
+        assert!((put - expected).abs() < 1e-4);
+        assert!(call < 0.0 && put > 0.0);
+
+        // out of the money calls pay far more decay per unit of exposure than in the money ones
+        assert!(ratio(OptionType::Call, 80.0) < ratio(OptionType::Call, 50.0));
+    }
+
+    #[test]
+    fn test_hedged_theta() {
+        let p = SAMPLE_PARAMS;
+        let (s, t, r, q, v) = (p.s0, p.t, p.r, p.q, p.sigma);
+        let gamma_decay = 0.5 * v * v * s * s * gamma(s, p.x, t, r, q, v);
+        let call = hedged_theta(OptionType::Call, s, p.x, t, r, q, v, p.days_per_year);
+        let expected = (r * euro_call(s, p.x, t, r, q, v) - gamma_decay) / p.days_per_year;
+        assert!((call - expected).abs() < 1e-12);
+        let put = hedged_theta(OptionType::Put, s, p.x, t, r, q, v, p.days_per_year);
+        let expected = (r * euro_put(s, p.x, t, r, q, v) - gamma_decay) / p.days_per_year;
+        assert!((put - expected).abs() < 1e-12);
+
+        // with the rate below the yield the short hedge costs carry, so the call decays faster hedged
+        assert!(call < theta_call(s, p.x, t, r, q, v, p.days_per_year));
+    }
+
+    #[test]
+    fn test_charm() {
+        let p = SAMPLE_PARAMS;
+        // delta one day closer to expiry
+        let t = p.t - 1.0 / p.days_per_year;
+        let call = delta_call(p.s0, p.x, t, p.r, p.q, p.sigma)
+            - delta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let charm = charm_call(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
+        assert!((charm - call).abs() < 0.0001);
+
+        let put = delta_put(p.s0, p.x, t, p.r, p.q, p.sigma)
+            - delta_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let charm = charm_put(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
+        assert!((charm - put).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_charm_reference() {
+        let p = SAMPLE_PARAMS;
+        let charm = |kind: OptionType, days_per_year: f64| {
+            let f = match kind {
+                OptionType::Call => charm_call,
+                OptionType::Put => charm_put,
+            };
+            f(p.s0, p.x, p.t, p.r, p.q, p.sigma, days_per_year)
+        };
+        // quoted per year the fixture values are large enough for the usual tolerance
+        assert!((charm(OptionType::Call, 1.0) - E_CHARM_CALL).abs() < 0.001);
+        assert!((charm(OptionType::Put, 1.0) - E_CHARM_PUT).abs() < 0.001);
+        // and per calendar day they are a 365th of that
+        let per_day = charm(OptionType::Call, p.days_per_year);
+        assert!((per_day - E_CHARM_CALL / p.days_per_year).abs() < 0.001 / p.days_per_year);
+        let per_day = charm(OptionType::Put, p.days_per_year);
+        assert!((per_day - E_CHARM_PUT / p.days_per_year).abs() < 0.001 / p.days_per_year);
+    }
+
+    #[test]
+    fn test_greeks_bundle_matches_standalone() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let call = greeks_call(s, x, t, r, q, v, p.days_per_year);
+        assert!((call.delta - delta_call(s, x, t, r, q, v)).abs() < 1e-12);
+        assert!((call.gamma - gamma(s, x, t, r, q, v)).abs() < 1e-12);
+        assert!((call.theta - theta_call(s, x, t, r, q, v, p.days_per_year)).abs() < 1e-12);
+        assert!((call.vega - vega(s, x, t, r, q, v)).abs() < 1e-12);
+        assert!((call.rho - rho_call(s, x, t, r, q, v)).abs() < 1e-12);
+
+        let put = greeks_put(s, x, t, r, q, v, p.days_per_year);
+        assert!((put.delta - delta_put(s, x, t, r, q, v)).abs() < 1e-12);
+        assert!((put.gamma - gamma(s, x, t, r, q, v)).abs() < 1e-12);
+        assert!((put.theta - theta_put(s, x, t, r, q, v, p.days_per_year)).abs() < 1e-12);
+        assert!((put.vega - vega(s, x, t, r, q, v)).abs() < 1e-12);
+        assert!((put.rho - rho_put(s, x, t, r, q, v)).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,59 @@
+// Module containing greeks of a linear forward position, so a hedge leg can be combined with options
+use std::f64::consts::E;
+
+use greeks::Greeks;
+
+/// Calculates the greeks of a long forward contract to buy the underlying at `x` on expiry
+///
+/// The forward is worth `s0 * e^(-qt) - x * e^(-rt)`, so delta is `e^(-qt)`, gamma and vega are zero,
+/// and theta and rho come from the discounting of each leg. Units follow `Greeks`.
+///
+/// # Arguments
+/// * `s0` - The underlying price
+/// * `x` - The delivery price of the forward
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `days_per_year` - the number of calendar days in the year
+pub fn forward_greeks(s0: f64, x: f64, t: f64, r: f64, q: f64, days_per_year: f64) -> Greeks {
+    let asset = s0 * E.powf(-q * t);
+    let cash = x * E.powf(-r * t);
+    Greeks {
+        delta: E.powf(-q * t),
+        gamma: 0.0,
+        theta: (1.0 / days_per_year) * (q * asset - r * cash),
+        vega: 0.0,
+        rho: (1.0 / 100.0) * t * cash,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use greeks::*;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    fn forward_value(s0: f64, t: f64, r: f64) -> f64 {
+        let p = SAMPLE_PARAMS;
+        s0 * (-p.q * t).exp() - p.x * (-r * t).exp()
+    }
+
+    #[test]
+    fn test_forward_greeks() {
+        let p = SAMPLE_PARAMS;
+        let greeks = forward_greeks(p.s0, p.x, p.t, p.r, p.q, p.days_per_year);
+        assert_eq!(greeks.gamma, 0.0);
+        assert_eq!(greeks.vega, 0.0);
+        assert!(greeks.delta > 0.0 && greeks.delta < 1.0);
+
+        let h = 1e-6;
+        let e_theta = -(forward_value(p.s0, p.t + h, p.r) - forward_value(p.s0, p.t - h, p.r))
+            / (2.0 * h)
+            / p.days_per_year;
+        let e_rho = (forward_value(p.s0, p.t, p.r + h) - forward_value(p.s0, p.t, p.r - h))
+            / (2.0 * h)
+            / 100.0;
+        assert!((greeks.theta - e_theta).abs() < 1e-6);
+        assert!((greeks.rho - e_rho).abs() < 1e-6);
+    }
+}
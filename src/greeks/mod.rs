@@ -1,9 +1,25 @@
+mod asian;
+mod binary;
+mod bundle;
+mod carry;
 mod concentrated_liquidity;
 mod first;
+mod forward;
+mod profile;
+mod risk;
 mod second;
 mod squeeks;
+mod strike;
 
+pub use self::asian::*;
+pub use self::binary::*;
+pub use self::bundle::*;
+pub use self::carry::*;
 pub use self::concentrated_liquidity::*;
 pub use self::first::*;
+pub use self::forward::*;
+pub use self::profile::*;
+pub use self::risk::*;
 pub use self::second::*;
 pub use self::squeeks::*;
+pub use self::strike::*;
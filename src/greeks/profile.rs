@@ -0,0 +1,308 @@
+// Module containing greeks evaluated across a grid of strikes
+use common::OptionType;
+use greeks::{
+    delta_call, delta_put, gamma, gbsm_greeks, rho_call, rho_put, theta_call, theta_put, vega,
+};
+
+/// Calculates the ratio of theta to gamma of a call at each strike
+///
+/// The ratio is the theta paid per unit of gamma held. Black-Scholes ties the two together through
+/// `theta = -sigma^2 * s0^2 * gamma / 2` plus carry terms, so with zero rates and yield the ratio is
+/// `-sigma^2 * s0^2 / (2 * days_per_year)` at every strike and any differences between strikes come from carry.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn theta_gamma_profile(
+    s0: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> Vec<f64> {
+    strikes
+        .iter()
+        .map(|&x| {
+            let greeks = gbsm_greeks(OptionType::Call, s0, x, t, r, r - q, sigma, days_per_year);
+            greeks.theta / greeks.gamma
+        })
+        .collect()
+}
+
+/// Calculates the delta of a call at each strike into a caller provided buffer
+///
+/// Panics if `out` is not the same length as `strikes`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `out` - Buffer receiving the greek at each strike, the same length as `strikes`
+pub fn delta_call_slice(
+    s0: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    out: &mut [f64],
+) {
+    fill_strikes(strikes, out, |x| delta_call(s0, x, t, r, q, sigma));
+}
+
+/// Calculates the delta of a put at each strike into a caller provided buffer
+///
+/// Panics if `out` is not the same length as `strikes`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `out` - Buffer receiving the greek at each strike, the same length as `strikes`
+pub fn delta_put_slice(
+    s0: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    out: &mut [f64],
+) {
+    fill_strikes(strikes, out, |x| delta_put(s0, x, t, r, q, sigma));
+}
+
+/// Calculates the gamma of a call or put at each strike into a caller provided buffer
+///
+/// Panics if `out` is not the same length as `strikes`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `out` - Buffer receiving the greek at each strike, the same length as `strikes`
+pub fn gamma_slice(s0: f64, strikes: &[f64], t: f64, r: f64, q: f64, sigma: f64, out: &mut [f64]) {
+    fill_strikes(strikes, out, |x| gamma(s0, x, t, r, q, sigma));
+}
+
+/// Calculates the vega of a call or put at each strike into a caller provided buffer
+///
+/// Panics if `out` is not the same length as `strikes`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `out` - Buffer receiving the greek at each strike, the same length as `strikes`
+pub fn vega_slice(s0: f64, strikes: &[f64], t: f64, r: f64, q: f64, sigma: f64, out: &mut [f64]) {
+    fill_strikes(strikes, out, |x| vega(s0, x, t, r, q, sigma));
+}
+
+/// Calculates the theta of a call at each strike into a caller provided buffer
+///
+/// Panics if `out` is not the same length as `strikes`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+/// * `out` - Buffer receiving the greek at each strike, the same length as `strikes`
+#[allow(clippy::too_many_arguments)]
+pub fn theta_call_slice(
+    s0: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+    out: &mut [f64],
+) {
+    fill_strikes(strikes, out, |x| {
+        theta_call(s0, x, t, r, q, sigma, days_per_year)
+    });
+}
+
+/// Calculates the theta of a put at each strike into a caller provided buffer
+///
+/// Panics if `out` is not the same length as `strikes`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+/// * `out` - Buffer receiving the greek at each strike, the same length as `strikes`
+#[allow(clippy::too_many_arguments)]
+pub fn theta_put_slice(
+    s0: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+    out: &mut [f64],
+) {
+    fill_strikes(strikes, out, |x| {
+        theta_put(s0, x, t, r, q, sigma, days_per_year)
+    });
+}
+
+/// Calculates the rho of a call at each strike into a caller provided buffer
+///
+/// Panics if `out` is not the same length as `strikes`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `out` - Buffer receiving the greek at each strike, the same length as `strikes`
+pub fn rho_call_slice(
+    s0: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    out: &mut [f64],
+) {
+    fill_strikes(strikes, out, |x| rho_call(s0, x, t, r, q, sigma));
+}
+
+/// Calculates the rho of a put at each strike into a caller provided buffer
+///
+/// Panics if `out` is not the same length as `strikes`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `strikes` - The strikes to evaluate
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `out` - Buffer receiving the greek at each strike, the same length as `strikes`
+pub fn rho_put_slice(
+    s0: f64,
+    strikes: &[f64],
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    out: &mut [f64],
+) {
+    fill_strikes(strikes, out, |x| rho_put(s0, x, t, r, q, sigma));
+}
+
+// Writes the greek at each strike into 'out', without allocating
+fn fill_strikes(strikes: &[f64], out: &mut [f64], greek: impl Fn(f64) -> f64) {
+    assert_eq!(strikes.len(), out.len());
+    for (slot, &x) in out.iter_mut().zip(strikes.iter()) {
+        *slot = greek(x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use greeks::*;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const STRIKES: [f64; 7] = [50.0, 55.0, 60.0, 65.0, 70.0, 75.0, 80.0];
+
+    #[test]
+    fn test_theta_gamma_profile_without_carry() {
+        let p = SAMPLE_PARAMS;
+        let profile = theta_gamma_profile(p.s0, &STRIKES, p.t, 0.0, 0.0, p.sigma, p.days_per_year);
+        let expected = -p.sigma.powf(2.0) * p.s0.powf(2.0) / (2.0 * p.days_per_year);
+        for ratio in profile {
+            assert!((ratio - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_theta_gamma_profile_with_carry() {
+        let p = SAMPLE_PARAMS;
+        let profile = theta_gamma_profile(p.s0, &STRIKES, p.t, p.r, p.q, p.sigma, p.days_per_year);
+        let gamma_rent = -p.sigma.powf(2.0) * p.s0.powf(2.0) / (2.0 * p.days_per_year);
+        assert_eq!(profile.len(), STRIKES.len());
+        // near the money carry only nudges the ratio away from the pure gamma rent
+        assert!((profile[3] / gamma_rent - 1.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_slices_match_scalar() {
+        let p = SAMPLE_PARAMS;
+        let (s, t, r, q, v) = (p.s0, p.t, p.r, p.q, p.sigma);
+        let mut out = [0.0; 7];
+
+        delta_call_slice(s, &STRIKES, t, r, q, v, &mut out);
+        for (&x, &greek) in STRIKES.iter().zip(out.iter()) {
+            assert_eq!(greek, delta_call(s, x, t, r, q, v));
+        }
+        delta_put_slice(s, &STRIKES, t, r, q, v, &mut out);
+        for (&x, &greek) in STRIKES.iter().zip(out.iter()) {
+            assert_eq!(greek, delta_put(s, x, t, r, q, v));
+        }
+        gamma_slice(s, &STRIKES, t, r, q, v, &mut out);
+        for (&x, &greek) in STRIKES.iter().zip(out.iter()) {
+            assert_eq!(greek, gamma(s, x, t, r, q, v));
+        }
+        vega_slice(s, &STRIKES, t, r, q, v, &mut out);
+        for (&x, &greek) in STRIKES.iter().zip(out.iter()) {
+            assert_eq!(greek, vega(s, x, t, r, q, v));
+        }
+        theta_call_slice(s, &STRIKES, t, r, q, v, p.days_per_year, &mut out);
+        for (&x, &greek) in STRIKES.iter().zip(out.iter()) {
+            assert_eq!(greek, theta_call(s, x, t, r, q, v, p.days_per_year));
+        }
+        theta_put_slice(s, &STRIKES, t, r, q, v, p.days_per_year, &mut out);
+        for (&x, &greek) in STRIKES.iter().zip(out.iter()) {
+            assert_eq!(greek, theta_put(s, x, t, r, q, v, p.days_per_year));
+        }
+        rho_call_slice(s, &STRIKES, t, r, q, v, &mut out);
+        for (&x, &greek) in STRIKES.iter().zip(out.iter()) {
+            assert_eq!(greek, rho_call(s, x, t, r, q, v));
+        }
+        rho_put_slice(s, &STRIKES, t, r, q, v, &mut out);
+        for (&x, &greek) in STRIKES.iter().zip(out.iter()) {
+            assert_eq!(greek, rho_put(s, x, t, r, q, v));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_length_mismatch() {
+        let p = SAMPLE_PARAMS;
+        let mut out = [0.0; 3];
+        delta_call_slice(p.s0, &STRIKES, p.t, p.r, p.q, p.sigma, &mut out);
+    }
+}
@@ -0,0 +1,80 @@
+// Module containing the spot and volatility sensitivities used by parametric risk models
+use contract::OptionContract;
+use greeks::*;
+
+/// Gradient and Hessian of an option's value with respect to spot and volatility, plus theta
+///
+/// The volatility axis is measured in volatility points throughout so the entries form a consistent
+/// second order expansion `dV = delta dS + vega dv + gamma dS^2 / 2 + vanna dS dv + vomma dv^2 / 2`
+/// with `dv` in points. `vega` matches the standalone function, `vanna` is the standalone value / 100
+/// and `vomma` is the standalone value / 100. `theta` is per calendar day.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RiskSensitivities {
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub vanna: f64,
+    pub vomma: f64,
+    pub theta: f64,
+}
+
+/// Calculates the spot and volatility gradient and Hessian of an option for delta-gamma VaR
+///
+/// # Arguments
+/// * `contract` - The option to evaluate
+/// * `days_per_year` - the number of calendar days in the year
+pub fn risk_factor_sensitivities(
+    contract: &OptionContract,
+    days_per_year: f64,
+) -> RiskSensitivities {
+    let c = contract;
+    RiskSensitivities {
+        delta: delta(c.kind, c.s0, c.x, c.t, c.r, c.q, c.sigma),
+        gamma: gamma(c.s0, c.x, c.t, c.r, c.q, c.sigma),
+        vega: vega(c.s0, c.x, c.t, c.r, c.q, c.sigma),
+        vanna: vanna(c.s0, c.x, c.t, c.r, c.q, c.sigma) / 100.0,
+        vomma: vomma(c.s0, c.x, c.t, c.r, c.q, c.sigma) / 100.0,
+        theta: theta(c.kind, c.s0, c.x, c.t, c.r, c.q, c.sigma, days_per_year),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use contract::OptionContract;
+    use greeks::*;
+    use price::euro_put;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_risk_factor_sensitivities() {
+        let p = SAMPLE_PARAMS;
+        let contract = OptionContract::new(OptionType::Put, p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let risk = risk_factor_sensitivities(&contract, p.days_per_year);
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert_eq!(risk.delta, delta_put(s, x, t, r, q, v));
+        assert_eq!(risk.gamma, gamma(s, x, t, r, q, v));
+        assert_eq!(risk.vega, vega(s, x, t, r, q, v));
+        assert_eq!(risk.vanna, vanna(s, x, t, r, q, v) / 100.0);
+        assert_eq!(risk.vomma, vomma(s, x, t, r, q, v) / 100.0);
+        assert_eq!(risk.theta, theta_put(s, x, t, r, q, v, p.days_per_year));
+    }
+
+    #[test]
+    fn test_risk_factor_expansion() {
+        let p = SAMPLE_PARAMS;
+        // the second order expansion tracks a joint move in spot and volatility
+        let contract = OptionContract::new(OptionType::Put, p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let risk = risk_factor_sensitivities(&contract, p.days_per_year);
+        let (ds, dv) = (1.0, 2.0);
+        let predicted = risk.delta * ds
+            + risk.vega * dv
+            + risk.gamma * ds * ds / 2.0
+            + risk.vanna * ds * dv
+            + risk.vomma * dv * dv / 2.0;
+        let actual =
+            euro_put(p.s0 + ds, p.x, p.t, p.r, p.q, p.sigma + dv / 100.0) - contract.price();
+        assert!((predicted - actual).abs() < 0.002);
+    }
+}
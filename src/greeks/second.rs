@@ -2,6 +2,8 @@
 use std::f64::consts::E;
 
 use common::*;
+use greeks::{vega_d1, SecondOrderGreeks};
+use stats::{cnd, pdf};
 
 /// Calculates the Gamma for an option
 ///
@@ -19,39 +21,549 @@ pub fn gamma(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
     return gamma_d1(s0, t, q, sigma, d1);
 }
 
+/// Calculates the gamma of an option on a leveraged or rebasing token with respect to the reference asset
+///
+/// The token moves by `k * dS` for a move `dS` in the reference asset, so gamma against the reference is `k^2` times
+/// gamma against the token, the counterpart of `delta_levered`.
+///
+/// # Arguments
+/// * `k` - leverage factor of the token, e.g. 3 for a 3x token
+/// * `s0` - The price of the token
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility of the token
+pub fn gamma_levered(k: f64, s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    k.powf(2.0) * gamma(s0, x, t, r, q, sigma)
+}
+
+/// Calculates the dollar gamma profit and loss of an option for a percentage move in the underlying
+///
+/// The second order term of the Taylor expansion, `0.5 * gamma * (s0 * pct)^2`, is what a delta hedged option earns
+/// on the move and the same for a rise or a fall. Dealers quote it for a 1% move.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `pct` - size of the move as a fraction of the underlying price, 0.01 for 1%
+pub fn gamma_pnl_per_pct(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64, pct: f64) -> f64 {
+    0.5 * gamma(s0, x, t, r, q, sigma) * (s0 * pct).powf(2.0)
+}
+
+/// Calculates how many days a delta hedged long option must be held for its gamma P&L to cover the theta paid
+///
+/// Hedged daily and financed at `r`, the option earns `0.5 * gamma * s0^2 * sigma_realized^2` a year from gamma
+/// while theta net of financing charges `0.5 * gamma * s0^2 * sigma_implied^2`, so gamma out-earns theta by
+/// `0.5 * gamma * s0^2 * (sigma_realized^2 - sigma_implied^2)` a year. The theta to be paid over the life of the
+/// option is its time value, the premium above intrinsic, and the break-even is that time value divided by the
+/// daily edge with the greeks held at today's values. Infinite when realized volatility is at or below implied, as
+/// gamma never gets ahead of theta, and zero for an option with no time value left.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma_implied` - volatility the option is priced at
+/// * `sigma_realized` - volatility expected to be realized while hedging
+/// * `days_per_year` - the number of calendar days in the year
+#[allow(clippy::too_many_arguments)]
+pub fn break_even_holding_days(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma_implied: f64,
+    sigma_realized: f64,
+    days_per_year: f64,
+) -> f64 {
+    let intrinsic = match kind {
+        OptionType::Call => (s0 - x).max(0.0),
+        OptionType::Put => (x - s0).max(0.0),
+    };
+    let time_value = ::price::price(kind, s0, x, t, r, q, sigma_implied) - intrinsic;
+    if time_value <= 0.0 {
+        return 0.0;
+    }
+    let dollar_gamma = 0.5 * gamma(s0, x, t, r, q, sigma_implied) * s0.powf(2.0);
+    let edge = dollar_gamma * (sigma_realized.powf(2.0) - sigma_implied.powf(2.0)) / days_per_year;
+    if edge <= 0.0 {
+        return f64::INFINITY;
+    }
+    time_value / edge
+}
+
 pub fn gamma_d1(s0: f64, t: f64, q: f64, sigma: f64, d1: f64) -> f64 {
     let arg1 = E.powf(-(q * t)) / (s0 * sigma * (t.sqrt()));
-    let arg2 = one_over_sqrt_pi();
-    let arg3 = E.powf((-d1).powf(2.0)) / 2.0;
-    return arg1 * arg2 * arg3;
+    return arg1 * pdf(d1);
+}
+
+/// Calculates the Vanna for an option
+///
+/// Vanna measures the rate of change in the delta with respect to the change in volatility,
+/// equivalently the change in vega with respect to the underlying price. It is the same for calls and puts.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn vanna(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    let pdf = pdf(d1);
+    -E.powf(-(q * t)) * pdf * d2 / sigma
+}
+
+/// Calculates the Vomma for an option, also known as Volga
+///
+/// Vomma measures the rate of change in the vega with respect to the change in volatility.
+/// Like `vega` it is divided by 100, so it is the change in value per volatility point per unit of volatility.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn vomma(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    vega_d1(s0, t, q, d1) * d1 * d2 / sigma
+}
+
+/// Calculates the Veta for an option
+///
+/// Veta measures the change in vega over the passage of one calendar day. Like `vega` it is per volatility point.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn veta(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64, days_per_year: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    let arg = q + (r - q) * d1 / (sigma * t.sqrt()) - (1.0 + d1 * d2) / (2.0 * t);
+    vega_d1(s0, t, q, d1) * arg / days_per_year
+}
+
+/// Calculates the Vera for an option
+///
+/// Vera measures the rate of change in rho with respect to the change in volatility. It is the same for calls and puts.
+/// Like `rho` it is divided by 100, so it is the change in rho per rate point per unit of volatility.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn vera(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    let pdf = pdf(d2);
+    -(1.0 / 100.0) * x * t * E.powf(-r * t) * pdf * d1 / sigma
+}
+
+/// Calculates the Speed for an option
+///
+/// Speed measures the rate of change in the gamma with respect to the change in the underlying price.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn speed(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    -gamma_d1(s0, t, q, sigma, d1) / s0 * (d1 / (sigma * t.sqrt()) + 1.0)
+}
+
+/// Calculates the Zomma for an option
+///
+/// Zomma measures the rate of change in the gamma with respect to the change in volatility.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn zomma(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    gamma_d1(s0, t, q, sigma, d1) * (d1 * d2 - 1.0) / sigma
+}
+
+/// Calculates the Color for an option, also known as gamma decay
+///
+/// Color measures the change in gamma over the passage of one calendar day.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+pub fn color(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64, days_per_year: f64) -> f64 {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    let sigma_sqrt_t = sigma * t.sqrt();
+    let arg = 2.0 * q * t + 1.0 + (2.0 * (r - q) * t - d2 * sigma_sqrt_t) / sigma_sqrt_t * d1;
+    gamma_d1(s0, t, q, sigma, d1) / (2.0 * t) * arg / days_per_year
+}
+
+/// Calculates every second order greek of an option in one pass
+///
+/// Shares `d1`, `d2`, the normal density and the discount factors between the greeks, which otherwise
+/// each recompute them. Matches the standalone functions field for field.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put, which only changes charm
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `days_per_year` - the number of calendar days in the year
+#[allow(clippy::too_many_arguments)]
+pub fn second_order_greeks(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    days_per_year: f64,
+) -> SecondOrderGreeks {
+    let d1 = d1(s0, x, t, r, q, sigma);
+    let d2 = d2_d1(t, sigma, d1);
+    let sqrt_t = t.sqrt();
+    let sigma_sqrt_t = sigma * sqrt_t;
+    let asset_discount = E.powf(-q * t);
+    let pdf_d1 = pdf(d1);
+    let pdf_d2 = pdf(d2);
+    let gamma = asset_discount * pdf_d1 / (s0 * sigma_sqrt_t);
+    let vega = (1.0 / 100.0) * s0 * asset_discount * sqrt_t * pdf_d1;
+
+    let drift_term = (2.0 * (r - q) * t - d2 * sigma_sqrt_t) / (2.0 * t * sigma_sqrt_t);
+    let charm_carry = match kind {
+        OptionType::Call => q * asset_discount * cnd(d1),
+        OptionType::Put => -q * asset_discount * cnd(-d1),
+    };
+
+    SecondOrderGreeks {
+        gamma,
+        vanna: -asset_discount * pdf_d1 * d2 / sigma,
+        charm: (charm_carry - asset_discount * pdf_d1 * drift_term) / days_per_year,
+        vomma: vega * d1 * d2 / sigma,
+        veta: vega * (q + (r - q) * d1 / sigma_sqrt_t - (1.0 + d1 * d2) / (2.0 * t))
+            / days_per_year,
+        vera: -(1.0 / 100.0) * x * t * E.powf(-r * t) * pdf_d2 * d1 / sigma,
+        speed: -gamma / s0 * (d1 / sigma_sqrt_t + 1.0),
+        zomma: gamma * (d1 * d2 - 1.0) / sigma,
+        color: gamma / (2.0 * t) * (2.0 * q * t + 1.0 + 2.0 * t * drift_term * d1) / days_per_year,
+    }
+}
+
+/// Calculates the rate convexity of an option, the second derivative of its price with respect to the interest rate
+///
+/// Differentiating rho `x * t * e^(-rt) * N(d2)` picks up both the discounting, `-t * N(d2)`, and the move in `d2`,
+/// `n(d2) * sqrt(t) / sigma`. Unlike `rho` it is not scaled, so multiply by `dr^2 / 2` with `dr` a decimal rate move.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn rate_convexity(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> f64 {
+    let d2 = d2(s0, x, t, r, q, sigma);
+    let pdf = pdf(d2);
+    let discounting = match kind {
+        OptionType::Call => -t * cnd(d2),
+        OptionType::Put => t * cnd(-d2),
+    };
+    x * t * E.powf(-r * t) * (discounting + pdf * t.sqrt() / sigma)
 }
 
 #[cfg(test)]
 mod tests {
 
+    use common::OptionType;
+    use finite_diff::finite_diff;
     use greeks::*;
+    use price::{euro_call, euro_put};
+    use test_fixtures::SAMPLE_PARAMS;
 
-    const UNDERLYING: f64 = 64.68;
-    const STRIKE: f64 = 65.00;
-    const VOL: f64 = 0.5051;
-    const INTEREST_RATE: f64 = 0.0150;
-    const DIV_YIELD: f64 = 0.0210;
-    const DAYS_PER_YEAR: f64 = 365.0;
-    const TIME_TO_EXPIRY: f64 = 23.0 / DAYS_PER_YEAR;
-
-    const E_GAMMA: f64 = 0.0243;
+    const E_GAMMA: f64 = 0.0486;
+    const E_VANNA: f64 = 0.0830;
+    const E_VOMMA: f64 = -0.00029;
 
     #[test]
     fn test_gamma() {
-        let gamma = gamma(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let gamma = gamma(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (gamma - E_GAMMA).abs();
         assert!(abs < 0.001);
     }
+
+    #[test]
+    fn test_levered_greeks() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        // an unlevered token has the standard greeks
+        assert_eq!(
+            delta_levered(1.0, OptionType::Call, s, x, t, r, q, v),
+            delta_call(s, x, t, r, q, v)
+        );
+        assert_eq!(
+            delta_levered(1.0, OptionType::Put, s, x, t, r, q, v),
+            delta_put(s, x, t, r, q, v)
+        );
+        assert_eq!(
+            gamma_levered(1.0, s, x, t, r, q, v),
+            gamma(s, x, t, r, q, v)
+        );
+
+        // a 3x token triples delta and multiplies gamma by nine
+        let delta = delta_levered(3.0, OptionType::Call, s, x, t, r, q, v);
+        assert!((delta - 3.0 * delta_call(s, x, t, r, q, v)).abs() < 1e-12);
+        let levered_gamma = gamma_levered(3.0, s, x, t, r, q, v);
+        assert!((levered_gamma - 9.0 * gamma(s, x, t, r, q, v)).abs() < 1e-12);
+
+        // matching a bump of the reference asset that moves the token three times as far
+        let h = 1e-3;
+        let token = |ds: f64| euro_call(s + 3.0 * ds, x, t, r, q, v);
+        let bumped_delta = (token(h) - token(-h)) / (2.0 * h);
+        assert!((bumped_delta - delta).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_gamma_pnl_per_pct() {
+        let p = SAMPLE_PARAMS;
+        let pnl = gamma_pnl_per_pct(p.s0, p.x, p.t, p.r, p.q, p.sigma, 0.01);
+        let expected = 0.5 * E_GAMMA * (0.01 * p.s0).powf(2.0);
+        assert!((pnl - expected).abs() < 1e-5);
+
+        // a delta hedged call earns about that much on a 1% move either way
+        let price = |s0: f64| euro_call(s0, p.x, p.t, p.r, p.q, p.sigma);
+        let delta = delta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        for &sign in [1.0, -1.0].iter() {
+            let ds = sign * 0.01 * p.s0;
+            let hedged = price(p.s0 + ds) - price(p.s0) - delta * ds;
+            assert!((hedged - pnl).abs() < 0.01 * pnl);
+        }
+    }
+
+    #[test]
+    fn test_break_even_holding_days() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q) = (p.s0, p.x, p.t, p.r, p.q);
+        let days = |kind: OptionType, realized: f64| {
+            break_even_holding_days(kind, s, x, t, r, q, p.sigma, realized, p.days_per_year)
+        };
+        // gamma at the implied volatility only pays back its own theta
+        for kind in [OptionType::Call, OptionType::Put].iter() {
+            assert_eq!(days(*kind, p.sigma), f64::INFINITY);
+            assert_eq!(days(*kind, 0.3), f64::INFINITY);
+        }
+
+        // the daily edge is the gamma P&L at realized volatility plus the theta and financing of the hedged call
+        let realized = 0.6;
+        let price = euro_call(s, x, t, r, q, p.sigma);
+        let delta = delta_call(s, x, t, r, q, p.sigma);
+        let gamma_pnl =
+            0.5 * gamma(s, x, t, r, q, p.sigma) * s * s * realized * realized / p.days_per_year;
+        let theta = theta_call(s, x, t, r, q, p.sigma, p.days_per_year);
+        let financing = (r * price - (r - q) * s * delta) / p.days_per_year;
+        let edge = gamma_pnl + theta - financing;
+        // out of the money the whole premium is time value
+        let call = days(OptionType::Call, realized);
+        assert!((call - price / edge).abs() < 1e-6 * call);
+        // the in the money put shares the edge but only its time value is paid away in theta
+        let put_time_value = euro_put(s, x, t, r, q, p.sigma) - (x - s);
+        let put = days(OptionType::Put, realized);
+        assert!((put - put_time_value / edge).abs() < 1e-6 * put);
+        // more realized volatility breaks even sooner
+        assert!(days(OptionType::Call, 0.8) < call);
+    }
+
+    #[test]
+    fn test_vanna() {
+        let p = SAMPLE_PARAMS;
+        let vanna = vanna(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        // central difference of call delta in volatility
+        let h = 0.0001;
+        let up = delta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma + h);
+        let down = delta_call(p.s0, p.x, p.t, p.r, p.q, p.sigma - h);
+        let abs = (vanna - (up - down) / (2.0 * h)).abs();
+        assert!(abs < 0.0001);
+    }
+
+    #[test]
+    fn test_vanna_reference() {
+        let p = SAMPLE_PARAMS;
+        let vanna = vanna(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let abs = (vanna - E_VANNA).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_vomma() {
+        let p = SAMPLE_PARAMS;
+        let vomma = vomma(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        // central difference of vega in volatility
+        let h = 0.0001;
+        let up = vega(p.s0, p.x, p.t, p.r, p.q, p.sigma + h);
+        let down = vega(p.s0, p.x, p.t, p.r, p.q, p.sigma - h);
+        let abs = (vomma - (up - down) / (2.0 * h)).abs();
+        assert!(abs < 0.00001);
+    }
+
+    #[test]
+    fn test_vomma_reference() {
+        let p = SAMPLE_PARAMS;
+        let vomma = vomma(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        // near the money vomma is tiny, so it is held to the precision of the reference value
+        let abs = (vomma - E_VOMMA).abs();
+        assert!(abs < 0.00001);
+    }
+
+    #[test]
+    fn test_rate_convexity() {
+        let p = SAMPLE_PARAMS;
+        // longer dated so the rate has a visible effect
+        let (t, h) = (2.0, 0.01);
+        let call = |r: f64| euro_call(p.s0, p.x, t, r, p.q, p.sigma);
+        let put = |r: f64| euro_put(p.s0, p.x, t, r, p.q, p.sigma);
+        for &kind in [OptionType::Call, OptionType::Put].iter() {
+            let convexity = rate_convexity(kind, p.s0, p.x, t, p.r, p.q, p.sigma);
+            let expected = match kind {
+                OptionType::Call => call(p.r + h) - 2.0 * call(p.r) + call(p.r - h),
+                OptionType::Put => put(p.r + h) - 2.0 * put(p.r) + put(p.r - h),
+            } / h.powf(2.0);
+            assert!((convexity - expected).abs() < 0.01);
+        }
+    }
+
+    // inputs in the order used by the finite difference engine
+    const PARAMS: [f64; 6] = [
+        SAMPLE_PARAMS.s0,
+        SAMPLE_PARAMS.x,
+        SAMPLE_PARAMS.t,
+        SAMPLE_PARAMS.r,
+        SAMPLE_PARAMS.q,
+        SAMPLE_PARAMS.sigma,
+    ];
+
+    // change of 'f' over the passage of one calendar day
+    fn one_day(f: impl Fn(&[f64]) -> f64) -> f64 {
+        let mut later = PARAMS;
+        later[2] -= 1.0 / SAMPLE_PARAMS.days_per_year;
+        f(&later) - f(&PARAMS)
+    }
+
+    #[test]
+    fn test_veta() {
+        let p = SAMPLE_PARAMS;
+        let veta = veta(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
+        let expected = one_day(|a| vega(a[0], a[1], a[2], a[3], a[4], a[5]));
+        assert!((veta - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_vera() {
+        let p = SAMPLE_PARAMS;
+        let vera = vera(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let rho = |a: &[f64]| rho_call(a[0], a[1], a[2], a[3], a[4], a[5]);
+        let expected = finite_diff(rho, &PARAMS, 5, 0.001, 1);
+        assert!((vera - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_speed() {
+        let p = SAMPLE_PARAMS;
+        let speed = speed(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let gamma = |a: &[f64]| gamma(a[0], a[1], a[2], a[3], a[4], a[5]);
+        let expected = finite_diff(gamma, &PARAMS, 0, 0.01, 1);
+        assert!((speed - expected).abs() < 0.00001);
+    }
+
+    #[test]
+    fn test_zomma() {
+        let p = SAMPLE_PARAMS;
+        let zomma = zomma(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let gamma = |a: &[f64]| gamma(a[0], a[1], a[2], a[3], a[4], a[5]);
+        let expected = finite_diff(gamma, &PARAMS, 5, 0.001, 1);
+        assert!((zomma - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_color() {
+        let p = SAMPLE_PARAMS;
+        let color = color(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
+        let expected = one_day(|a| gamma(a[0], a[1], a[2], a[3], a[4], a[5]));
+        assert!((color - expected).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_second_order_greeks() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        for &kind in [OptionType::Call, OptionType::Put].iter() {
+            let bundle = second_order_greeks(kind, s, x, t, r, q, v, p.days_per_year);
+            let charm = match kind {
+                OptionType::Call => charm_call(s, x, t, r, q, v, p.days_per_year),
+                OptionType::Put => charm_put(s, x, t, r, q, v, p.days_per_year),
+            };
+            let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+            assert!(close(bundle.gamma, gamma(s, x, t, r, q, v)));
+            assert!(close(bundle.vanna, vanna(s, x, t, r, q, v)));
+            assert!(close(bundle.charm, charm));
+            assert!(close(bundle.vomma, vomma(s, x, t, r, q, v)));
+            assert!(close(bundle.veta, veta(s, x, t, r, q, v, p.days_per_year)));
+            assert!(close(bundle.vera, vera(s, x, t, r, q, v)));
+            assert!(close(bundle.speed, speed(s, x, t, r, q, v)));
+            assert!(close(bundle.zomma, zomma(s, x, t, r, q, v)));
+            assert!(close(
+                bundle.color,
+                color(s, x, t, r, q, v, p.days_per_year)
+            ));
+        }
+    }
 }
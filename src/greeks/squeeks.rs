@@ -1,11 +1,25 @@
 // Module containing functions for calculating squeeks
 // derived from squeethlab https://medium.com/opyn/how-to-think-about-squeeth-returns-8646fd57f559
 
+use greeks::Greeks;
+
 // Squeeth Constants
 const FUNDING_PERIOD: f64 = 17.5 / 365.0;
 const SCALING_FACTOR: f64 = 10000.0;
 const EULERS_NUMBER: f64 = 2.718281828459;
 
+// Log of the squeeth mark to index ratio, the variance iv^2 accrued over one funding period. Funding pays this
+// premium away once per funding period, so it sets both the price level and the decay of squeeth.
+fn funding_exponent(iv: f64) -> f64 {
+    power_funding_exponent(2.0, iv, FUNDING_PERIOD)
+}
+
+// Log of the mark to index ratio of a power perpetual, the convexity p(p-1)/2 * iv^2 of ETH^p accrued over one
+// funding period. For p = 2 it is the squeeth `funding_exponent`.
+fn power_funding_exponent(power: f64, iv: f64, funding_period: f64) -> f64 {
+    power * (power - 1.0) / 2.0 * iv.powf(2.0) * funding_period
+}
+
 /// Calculates squeeth price in USD
 ///
 /// # Arguments
@@ -16,10 +30,54 @@ const EULERS_NUMBER: f64 = 2.718281828459;
 /// # Return
 /// * sqth price
 pub fn sqth_to_usd(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
-    let sqth_price = (normalization_factor * (eth_price.powf(2.0)))
-        * EULERS_NUMBER.powf(iv.powf(2.0) * FUNDING_PERIOD)
-        / SCALING_FACTOR;
-    sqth_price
+    power_perp_price(
+        eth_price,
+        normalization_factor,
+        iv,
+        2.0,
+        SCALING_FACTOR,
+        FUNDING_PERIOD,
+    )
+}
+
+/// Calculates the price in USD of a power perpetual tracking ETH^power
+///
+/// The index `ETH^power / scaling_factor` is scaled by the normalization factor and marked up by the convexity
+/// premium `e^(p(p-1)/2 * iv^2 * funding_period)` that funding pays away. `sqth_to_usd` is the `power = 2` case.
+///
+/// # Arguments
+/// * `ETH Price` - ETH price in USD
+/// * `Normalization Factor` - Normalization factor for the underlying asset
+/// * `IV` - Implied volatility of the power perpetual
+/// * `Power` - Exponent of ETH the perpetual tracks, 2 for squeeth
+/// * `Scaling Factor` - Divisor of the index, 10000 for squeeth
+/// * `Funding Period` - Funding period as a percentage of the year, 17.5 / 365 for squeeth
+/// # Return
+/// * power perpetual price
+pub fn power_perp_price(
+    eth_price: f64,
+    normalization_factor: f64,
+    iv: f64,
+    power: f64,
+    scaling_factor: f64,
+    funding_period: f64,
+) -> f64 {
+    normalization_factor
+        * eth_price.powf(power)
+        * EULERS_NUMBER.powf(power_funding_exponent(power, iv, funding_period))
+        / scaling_factor
+}
+
+/// Calculates squeeth price in ETH
+///
+/// # Arguments
+/// * `ETH Price` - ETH price in USD
+/// * `Normalization Factor` - Normalization factor for the underlying asset
+/// * `IV` - Implied volatility
+/// # Return
+/// * sqth price denominated in ETH
+pub fn sqth_to_eth(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
+    sqth_to_usd(eth_price, normalization_factor, iv) / eth_price
 }
 
 /// Calculates delta of a sqth position
@@ -32,10 +90,14 @@ pub fn sqth_to_usd(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
 /// # Return
 /// * delta
 pub fn sqth_delta(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
-    let delta =
-        2.0 * normalization_factor * eth_price * EULERS_NUMBER.powf(iv.powf(2.0) * FUNDING_PERIOD)
-            / SCALING_FACTOR;
-    delta
+    power_perp_delta(
+        eth_price,
+        normalization_factor,
+        iv,
+        2.0,
+        SCALING_FACTOR,
+        FUNDING_PERIOD,
+    )
 }
 
 /// Calculates gamma of a sqth position
@@ -47,9 +109,8 @@ pub fn sqth_delta(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
 /// # Return
 /// * gamma
 pub fn sqth_gamma(normalization_factor: f64, iv: f64) -> f64 {
-    let gamma = 2.0 * normalization_factor * EULERS_NUMBER.powf(iv.powf(2.0) * FUNDING_PERIOD)
-        / SCALING_FACTOR;
-    gamma
+    // gamma of ETH^2 is the same at every ETH price, p(p-1) * normalization factor * e^(funding) / scaling
+    2.0 * normalization_factor * EULERS_NUMBER.powf(funding_exponent(iv)) / SCALING_FACTOR
 }
 
 /// Calculates theta of a sqth position / yr
@@ -62,8 +123,14 @@ pub fn sqth_gamma(normalization_factor: f64, iv: f64) -> f64 {
 /// # Return
 /// * theta
 pub fn sqth_theta(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
-    let theta = iv.powf(2.0) * sqth_to_usd(eth_price, normalization_factor, iv);
-    theta
+    power_perp_theta(
+        eth_price,
+        normalization_factor,
+        iv,
+        2.0,
+        SCALING_FACTOR,
+        FUNDING_PERIOD,
+    )
 }
 
 /// Calculates vega of a sqth position / yr
@@ -76,23 +143,254 @@ pub fn sqth_theta(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
 /// # Return
 /// * vega
 pub fn sqth_vega(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
-    let vega = 2.0 * iv * FUNDING_PERIOD * sqth_to_usd(eth_price, normalization_factor, iv);
-    vega
+    power_perp_vega(
+        eth_price,
+        normalization_factor,
+        iv,
+        2.0,
+        SCALING_FACTOR,
+        FUNDING_PERIOD,
+    )
+}
+
+/// Calculates vega of a sqth position per vol point
+///
+/// `sqth_vega` is the change in price for a change of 1.0 in IV, this is the change for a change of 0.01,
+/// matching the scaling of the vanilla option vega.
+///
+/// # Arguments
+/// * `ETH Price` - ETH price in USD
+/// * `Normalization Factor` - Normalization factor for the underlying asset
+/// * `IV` - Implied volatility
+/// # Return
+/// * vega per vol point
+pub fn sqth_vega_per_point(eth_price: f64, normalization_factor: f64, iv: f64) -> f64 {
+    sqth_vega(eth_price, normalization_factor, iv) / 100.0
+}
+
+/// Calculates delta of a power perpetual position, `power * price / eth_price`
+///
+/// # Arguments
+/// * `ETH Price` - ETH price in USD
+/// * `Normalization Factor` - Normalization factor for the underlying asset
+/// * `IV` - Implied volatility of the power perpetual
+/// * `Power` - Exponent of ETH the perpetual tracks, 2 for squeeth
+/// * `Scaling Factor` - Divisor of the index, 10000 for squeeth
+/// * `Funding Period` - Funding period as a percentage of the year, 17.5 / 365 for squeeth
+/// # Return
+/// * delta
+pub fn power_perp_delta(
+    eth_price: f64,
+    normalization_factor: f64,
+    iv: f64,
+    power: f64,
+    scaling_factor: f64,
+    funding_period: f64,
+) -> f64 {
+    let price = power_perp_price(
+        eth_price,
+        normalization_factor,
+        iv,
+        power,
+        scaling_factor,
+        funding_period,
+    );
+    power * price / eth_price
+}
+
+/// Calculates gamma of a power perpetual position, `power * (power - 1) * price / eth_price^2`
+///
+/// Constant in the ETH price for squeeth, growing with it for higher powers.
+///
+/// # Arguments
+/// * `ETH Price` - ETH price in USD
+/// * `Normalization Factor` - Normalization factor for the underlying asset
+/// * `IV` - Implied volatility of the power perpetual
+/// * `Power` - Exponent of ETH the perpetual tracks, 2 for squeeth
+/// * `Scaling Factor` - Divisor of the index, 10000 for squeeth
+/// * `Funding Period` - Funding period as a percentage of the year, 17.5 / 365 for squeeth
+/// # Return
+/// * gamma
+pub fn power_perp_gamma(
+    eth_price: f64,
+    normalization_factor: f64,
+    iv: f64,
+    power: f64,
+    scaling_factor: f64,
+    funding_period: f64,
+) -> f64 {
+    let price = power_perp_price(
+        eth_price,
+        normalization_factor,
+        iv,
+        power,
+        scaling_factor,
+        funding_period,
+    );
+    power * (power - 1.0) * price / eth_price.powf(2.0)
+}
+
+/// Calculates theta of a power perpetual position / yr
+///
+/// The funding premium is paid once per funding period, so the position pays `p(p-1)/2 * iv^2` of its price a year.
+///
+/// # Arguments
+/// * `ETH Price` - ETH price in USD
+/// * `Normalization Factor` - Normalization factor for the underlying asset
+/// * `IV` - Implied volatility of the power perpetual
+/// * `Power` - Exponent of ETH the perpetual tracks, 2 for squeeth
+/// * `Scaling Factor` - Divisor of the index, 10000 for squeeth
+/// * `Funding Period` - Funding period as a percentage of the year, 17.5 / 365 for squeeth
+/// # Return
+/// * theta
+pub fn power_perp_theta(
+    eth_price: f64,
+    normalization_factor: f64,
+    iv: f64,
+    power: f64,
+    scaling_factor: f64,
+    funding_period: f64,
+) -> f64 {
+    let price = power_perp_price(
+        eth_price,
+        normalization_factor,
+        iv,
+        power,
+        scaling_factor,
+        funding_period,
+    );
+    power_funding_exponent(power, iv, funding_period) / funding_period * price
+}
+
+/// Calculates vega of a power perpetual position / yr
+///
+/// The price scales with the exponential of the funding exponent, so vega is the price times the derivative of
+/// that exponent in iv, `p(p-1) * iv * funding_period`.
+///
+/// # Arguments
+/// * `ETH Price` - ETH price in USD
+/// * `Normalization Factor` - Normalization factor for the underlying asset
+/// * `IV` - Implied volatility of the power perpetual
+/// * `Power` - Exponent of ETH the perpetual tracks, 2 for squeeth
+/// * `Scaling Factor` - Divisor of the index, 10000 for squeeth
+/// * `Funding Period` - Funding period as a percentage of the year, 17.5 / 365 for squeeth
+/// # Return
+/// * vega
+pub fn power_perp_vega(
+    eth_price: f64,
+    normalization_factor: f64,
+    iv: f64,
+    power: f64,
+    scaling_factor: f64,
+    funding_period: f64,
+) -> f64 {
+    let price = power_perp_price(
+        eth_price,
+        normalization_factor,
+        iv,
+        power,
+        scaling_factor,
+        funding_period,
+    );
+    power * (power - 1.0) * iv * funding_period * price
+}
+
+/// Projects the normalization factor forward in time as funding is paid
+///
+/// Funding over a period `dt` scales the normalization factor by `(mark / index)^(-dt / FUNDING_PERIOD)`, and with
+/// `mark / index = e^(iv^2 * FUNDING_PERIOD)` the factor decays as `e^(-iv^2 * dt)`.
+///
+/// # Arguments
+/// * `Normalization Factor` - Current normalization factor
+/// * `IV` - Implied volatility
+/// * `dt` - Time step as a percentage of the year
+/// # Return
+/// * normalization factor after `dt`
+pub fn sqth_norm_factor_decay(normalization_factor: f64, iv: f64, dt: f64) -> f64 {
+    let funding_periods = dt / FUNDING_PERIOD;
+    normalization_factor * EULERS_NUMBER.powf(-funding_exponent(iv) * funding_periods)
+}
+
+/// Calculates the greeks of a crab position, long ETH and short squeeth
+///
+/// The crab holds enough ETH to offset the delta of its short squeeth, leaving it short gamma and vega
+/// while it earns funding. Units follow `Greeks`: theta is per calendar day and vega per vol point.
+/// `sqth_theta` is the funding a squeeth holder pays, so the short position receives it and theta is
+/// `+sqth_units * sqth_theta`, positive unlike a short option's. Rho is zero.
+///
+/// # Arguments
+/// * `ETH Price` - ETH price in USD
+/// * `Normalization Factor` - Normalization factor for the underlying asset
+/// * `IV` - Implied volatility
+/// * `ETH Units` - ETH held by the position
+/// * `Sqth Units` - squeeth sold by the position
+/// * `Days Per Year` - the number of calendar days in the year
+/// # Return
+/// * greeks of the combined position
+pub fn crab_greeks(
+    eth_price: f64,
+    normalization_factor: f64,
+    iv: f64,
+    eth_units: f64,
+    sqth_units: f64,
+    days_per_year: f64,
+) -> Greeks {
+    Greeks {
+        delta: eth_units - sqth_units * sqth_delta(eth_price, normalization_factor, iv),
+        gamma: -sqth_units * sqth_gamma(normalization_factor, iv),
+        theta: sqth_units * sqth_theta(eth_price, normalization_factor, iv) / days_per_year,
+        vega: -sqth_units * sqth_vega_per_point(eth_price, normalization_factor, iv),
+        rho: 0.0,
+    }
+}
+
+/// Converts a squeeth implied volatility into the equivalent flat ETH option implied volatility
+///
+/// A power perpetual paying continuous funding behaves like a strip of ETH^2 futures whose expiries are
+/// exponentially distributed with mean equal to the funding period `t`. With a flat option volatility
+/// `sigma` that strip trades at `1 / (1 - sigma^2 * t)` times index, while squeeth IV is quoted from
+/// `mark / index = e^(iv^2 * t)`, so `iv^2 * t = -ln(1 - sigma^2 * t)`. Squeeth IV therefore sits slightly above
+/// the option IV it corresponds to.
+///
+/// # Arguments
+/// * `Sqth IV` - Implied volatility of squeeth
+/// * `t` - Funding period as a percentage of the year (17.5 / 365 for squeeth)
+/// # Return
+/// * ETH option implied volatility
+pub fn sqth_iv_to_option_iv(sqth_iv: f64, t: f64) -> f64 {
+    ((1.0 - EULERS_NUMBER.powf(-sqth_iv.powf(2.0) * t)) / t).sqrt()
+}
+
+/// Converts a flat ETH option implied volatility into the equivalent squeeth implied volatility
+///
+/// Inverse of `sqth_iv_to_option_iv`, only defined while `sigma^2 * t < 1`.
+///
+/// # Arguments
+/// * `Option IV` - Implied volatility of ETH options
+/// * `t` - Funding period as a percentage of the year (17.5 / 365 for squeeth)
+/// # Return
+/// * squeeth implied volatility
+pub fn option_iv_to_sqth_iv(option_iv: f64, t: f64) -> f64 {
+    (-(1.0 - option_iv.powf(2.0) * t).ln() / t).sqrt()
 }
 
 #[cfg(test)]
 mod tests {
     use greeks::*;
 
+    const FUNDING_PERIOD: f64 = 17.5 / 365.0;
     const ETH_PRICE: f64 = 3500.0;
     const NORMALIZATION_FACTOR: f64 = 0.8;
     const IV: f64 = 0.9;
 
     const E_SQTH_TO_USD: f64 = 1018.807585;
+    const E_SQTH_TO_ETH: f64 = 0.2910878814;
     const E_DELTA: f64 = 0.5821757629;
     const E_GAMMA: f64 = 0.0001663359322;
     const E_THETA: f64 = 825.2341438;
     const E_VEGA: f64 = 87.92449021;
+    const E_OPTION_IV: f64 = 0.8913;
+    const E_NORM_FACTOR_ONE_DAY: f64 = 0.7982266;
 
     #[test]
     fn test_sqth_to_usd() {
@@ -101,6 +399,13 @@ mod tests {
         assert!(abs < 0.001);
     }
 
+    #[test]
+    fn test_sqth_to_eth() {
+        let sqth_to_eth = sqth_to_eth(ETH_PRICE, NORMALIZATION_FACTOR, IV);
+        let abs = (sqth_to_eth - E_SQTH_TO_ETH).abs();
+        assert!(abs < 0.000001);
+    }
+
     #[test]
     fn test_sqth_delta() {
         let delta = sqth_delta(ETH_PRICE, NORMALIZATION_FACTOR, IV);
@@ -128,4 +433,118 @@ mod tests {
         let abs = (vega - E_VEGA).abs();
         assert!(abs < 0.001);
     }
+
+    #[test]
+    fn test_sqth_vega_per_point() {
+        let per_point = sqth_vega_per_point(ETH_PRICE, NORMALIZATION_FACTOR, IV);
+        assert!((100.0 * per_point - E_VEGA).abs() < 0.001);
+
+        // a one point move in IV changes the price by roughly the per point vega
+        let bumped = sqth_to_usd(ETH_PRICE, NORMALIZATION_FACTOR, IV + 0.01)
+            - sqth_to_usd(ETH_PRICE, NORMALIZATION_FACTOR, IV);
+        assert!((bumped - per_point).abs() / per_point < 0.01);
+    }
+
+    #[test]
+    fn test_sqth_norm_factor_decay() {
+        let normalization_factor = sqth_norm_factor_decay(NORMALIZATION_FACTOR, IV, 1.0 / 365.0);
+        let abs = (normalization_factor - E_NORM_FACTOR_ONE_DAY).abs();
+        assert!(abs < 0.000001);
+    }
+
+    #[test]
+    fn test_crab_greeks() {
+        let sqth_units = 10.0;
+        let eth_units = sqth_units * sqth_delta(ETH_PRICE, NORMALIZATION_FACTOR, IV);
+        let crab = crab_greeks(
+            ETH_PRICE,
+            NORMALIZATION_FACTOR,
+            IV,
+            eth_units,
+            sqth_units,
+            365.0,
+        );
+        assert!(crab.delta.abs() < 0.000001);
+        assert!(crab.gamma < 0.0);
+        assert!(crab.vega < 0.0);
+
+        // one day of funding received on the short squeeth
+        let price = sqth_to_usd(ETH_PRICE, NORMALIZATION_FACTOR, IV);
+        assert!((crab.theta - sqth_units * price * IV.powf(2.0) / 365.0).abs() < 1e-9);
+
+        // a one point rise in IV costs the short squeeth roughly its vega
+        let bumped = sqth_to_usd(ETH_PRICE, NORMALIZATION_FACTOR, IV + 0.01) - price;
+        assert!((crab.vega + sqth_units * bumped).abs() / crab.vega.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_sqth_funding_matches_eth_variance() {
+        let price = sqth_to_usd(ETH_PRICE, NORMALIZATION_FACTOR, IV);
+        let daily_funding = sqth_theta(ETH_PRICE, NORMALIZATION_FACTOR, IV) / price / 365.0;
+        assert!((daily_funding - IV.powf(2.0) / 365.0).abs() < 1e-12);
+
+        // the normalization factor decays by the same daily rate
+        let decayed = sqth_norm_factor_decay(NORMALIZATION_FACTOR, IV, 1.0 / 365.0);
+        let decay = (NORMALIZATION_FACTOR / decayed).ln();
+        assert!((decay - daily_funding).abs() < 1e-9);
+
+        // over a funding period the premium paid is the ETH option variance, -ln(1 - sigma^2 * t)
+        let option_iv = sqth_iv_to_option_iv(IV, FUNDING_PERIOD);
+        let funding_over_period = daily_funding * 365.0 * FUNDING_PERIOD;
+        let option_variance = -(1.0 - option_iv.powf(2.0) * FUNDING_PERIOD).ln();
+        assert!((funding_over_period - option_variance).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sqth_iv_to_option_iv() {
+        let option_iv = sqth_iv_to_option_iv(IV, FUNDING_PERIOD);
+        let abs = (option_iv - E_OPTION_IV).abs();
+        assert!(abs < 0.001);
+        assert!(option_iv < IV);
+
+        let sqth_iv = option_iv_to_sqth_iv(option_iv, FUNDING_PERIOD);
+        assert!((sqth_iv - IV).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_power_perp_squeeth_specialization() {
+        let (eth, nf, iv) = (ETH_PRICE, NORMALIZATION_FACTOR, IV);
+        let (scaling, period) = (10000.0, FUNDING_PERIOD);
+        let price = power_perp_price(eth, nf, iv, 2.0, scaling, period);
+        assert!((price - E_SQTH_TO_USD).abs() < 0.001);
+        assert!((power_perp_delta(eth, nf, iv, 2.0, scaling, period) - E_DELTA).abs() < 0.001);
+        assert!((power_perp_gamma(eth, nf, iv, 2.0, scaling, period) - E_GAMMA).abs() < 0.001);
+        assert!((power_perp_theta(eth, nf, iv, 2.0, scaling, period) - E_THETA).abs() < 0.001);
+        assert!((power_perp_vega(eth, nf, iv, 2.0, scaling, period) - E_VEGA).abs() < 0.001);
+        for &eth in [0.5 * ETH_PRICE, ETH_PRICE, 2.0 * ETH_PRICE].iter() {
+            let gamma = power_perp_gamma(eth, nf, iv, 2.0, scaling, period);
+            assert!((gamma - sqth_gamma(nf, iv)).abs() < 1e-12 * gamma);
+        }
+    }
+
+    #[test]
+    fn test_power_perp_cubed() {
+        let (nf, iv, power, scaling, period) = (1.0, IV, 3.0, 1e8, FUNDING_PERIOD);
+        let price = |eth: f64, iv: f64| power_perp_price(eth, nf, iv, power, scaling, period);
+        // ETH^3 carries three times the convexity premium of ETH^2
+        let premium = (price(ETH_PRICE, IV) * scaling / ETH_PRICE.powf(3.0)).ln();
+        assert!((premium - 3.0 * IV.powf(2.0) * period).abs() < 1e-9);
+
+        let h = 0.01;
+        let delta = (price(ETH_PRICE + h, IV) - price(ETH_PRICE - h, IV)) / (2.0 * h);
+        let gamma = (price(ETH_PRICE + h, IV) - 2.0 * price(ETH_PRICE, IV)
+            + price(ETH_PRICE - h, IV))
+            / h.powf(2.0);
+        let vega = (price(ETH_PRICE, IV + 1e-6) - price(ETH_PRICE, IV - 1e-6)) / 2e-6;
+        let greek_delta = power_perp_delta(ETH_PRICE, nf, iv, power, scaling, period);
+        let greek_gamma = power_perp_gamma(ETH_PRICE, nf, iv, power, scaling, period);
+        let greek_vega = power_perp_vega(ETH_PRICE, nf, iv, power, scaling, period);
+        assert!((greek_delta - delta).abs() < 1e-6 * greek_delta);
+        assert!((greek_gamma - gamma).abs() < 1e-4 * greek_gamma);
+        assert!((greek_vega - vega).abs() < 1e-6 * greek_vega);
+
+        // funding pays three times the squeeth rate of iv^2 a year
+        let theta = power_perp_theta(ETH_PRICE, nf, iv, power, scaling, period);
+        assert!((theta - 3.0 * IV.powf(2.0) * price(ETH_PRICE, IV)).abs() < 1e-9 * theta);
+    }
 }
@@ -0,0 +1,107 @@
+// Module containing the strike sensitivities of European options, also known as dual greeks
+// Across a ladder of strikes they recover the risk-neutral distribution of the underlying at expiry
+// (Breeden-Litzenberger): `-dual_delta_call` is the discounted probability of finishing above the strike
+// and `dual_gamma` the discounted density there.
+use std::f64::consts::E;
+
+use common::*;
+use stats::{cnd, pdf};
+
+/// Calculates the dual delta of a call option, the change in its value per unit increase in the strike
+///
+/// Minus the discounted risk-neutral probability of the call finishing in the money.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn dual_delta_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d2 = d2(s0, x, t, r, q, sigma);
+    -E.powf(-r * t) * cnd(d2)
+}
+
+/// Calculates the dual delta of a put option, the change in its value per unit increase in the strike
+///
+/// The discounted risk-neutral probability of the put finishing in the money.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn dual_delta_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d2 = d2(s0, x, t, r, q, sigma);
+    E.powf(-r * t) * cnd(-d2)
+}
+
+/// Calculates the dual gamma of an option, the rate of change of its dual delta with respect to the strike
+///
+/// The same for calls and puts, and equal to the discounted risk-neutral density of the underlying at expiry
+/// evaluated at the strike. Dividing the second difference of quoted prices across a strike ladder by the discount
+/// factor therefore gives the market's density, and this is its Black-Scholes counterpart at a single volatility.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn dual_gamma(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d2 = d2(s0, x, t, r, q, sigma);
+    E.powf(-r * t) * pdf(d2) / (x * sigma * t.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use greeks::*;
+    use price::{euro_call, euro_put};
+    use std::f64::consts::E;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const E_DUAL_DELTA_CALL: f64 = -0.4576;
+    const E_DUAL_DELTA_PUT: f64 = 0.5414;
+    const E_DUAL_GAMMA: f64 = 0.0481;
+
+    #[test]
+    fn test_dual_greeks_reference() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((dual_delta_call(s, x, t, r, q, v) - E_DUAL_DELTA_CALL).abs() < 0.001);
+        assert!((dual_delta_put(s, x, t, r, q, v) - E_DUAL_DELTA_PUT).abs() < 0.001);
+        assert!((dual_gamma(s, x, t, r, q, v) - E_DUAL_GAMMA).abs() < 0.001);
+        // put minus call is the discount factor, from put-call parity
+        let parity = dual_delta_put(s, x, t, r, q, v) - dual_delta_call(s, x, t, r, q, v);
+        assert!((parity - E.powf(-r * t)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dual_greeks_strike_ladder() {
+        let p = SAMPLE_PARAMS;
+        let (s, t, r, q, v) = (p.s0, p.t, p.r, p.q, p.sigma);
+        let call = |x: f64| euro_call(s, x, t, r, q, v);
+        let put = |x: f64| euro_put(s, x, t, r, q, v);
+        let h = 0.01;
+        for &x in [50.0, p.x, 80.0].iter() {
+            let call_slope = (call(x + h) - call(x - h)) / (2.0 * h);
+            let put_slope = (put(x + h) - put(x - h)) / (2.0 * h);
+            let butterfly = (call(x + h) - 2.0 * call(x) + call(x - h)) / (h * h);
+            assert!((dual_delta_call(s, x, t, r, q, v) - call_slope).abs() < 1e-6);
+            assert!((dual_delta_put(s, x, t, r, q, v) - put_slope).abs() < 1e-6);
+            assert!((dual_gamma(s, x, t, r, q, v) - butterfly).abs() < 1e-5);
+        }
+
+        // the undiscounted density integrates to one across the strikes
+        let dx = 0.05;
+        let mass: f64 = (1..4000)
+            .map(|i| dual_gamma(s, i as f64 * dx, t, r, q, v) * dx)
+            .sum();
+        assert!((mass * E.powf(r * t) - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,529 @@
+// Module containing simulations of hedged positions along a price path
+use common::{pcs_equivalent_strike, OptionType};
+use contract::OptionContract;
+use greeks::{delta, gamma, sqth_delta, sqth_norm_factor_decay, sqth_to_usd, ClPosition};
+use portfolio::Position;
+use value::{call_at_expiry, put_at_expiry};
+
+/// Profit and loss of a hedged position over a simulated path
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HedgeResult {
+    /// change in value of the position being hedged
+    pub position_pnl: f64,
+    /// combined profit and loss of every hedging instrument
+    pub hedge_pnl: f64,
+    /// cumulative cost of trading the hedge
+    pub transaction_cost: f64,
+    /// `position_pnl + hedge_pnl - transaction_cost`, the hedging error after costs
+    pub net_pnl: f64,
+}
+
+/// Simulates a concentrated liquidity position hedged with long squeeth and a delta hedge in the base token
+///
+/// The liquidity position is short gamma, which long squeeth offsets. At each step the remaining delta of the
+/// position plus squeeth is neutralised with the base token. The squeeth normalization factor decays with funding
+/// between steps, so a flat path costs the funding paid.
+///
+/// # Arguments
+/// * `position` - The liquidity position, priced in the same units as the path
+/// * `price_path` - Base token prices at each rebalance, starting at the entry price
+/// * `sqth_units` - squeeth held, positive for long
+/// * `normalization_factor` - Normalization factor at the start of the path
+/// * `iv` - Implied volatility of squeeth
+/// * `dt` - Time between prices as a percentage of the year
+pub fn simulate_lp_squeeth_hedge(
+    position: &ClPosition,
+    price_path: &[f64],
+    sqth_units: f64,
+    normalization_factor: f64,
+    iv: f64,
+    dt: f64,
+) -> HedgeResult {
+    let mut squeeth_pnl = 0.0;
+    let mut delta_hedge_pnl = 0.0;
+    let mut nf = normalization_factor;
+    for step in price_path.windows(2) {
+        let (p0, p1) = (step[0], step[1]);
+        let next_nf = sqth_norm_factor_decay(nf, iv, dt);
+        let delta = position.delta(p0) + sqth_units * sqth_delta(p0, nf, iv);
+        delta_hedge_pnl -= delta * (p1 - p0);
+        squeeth_pnl += sqth_units * (sqth_to_usd(p1, next_nf, iv) - sqth_to_usd(p0, nf, iv));
+        nf = next_nf;
+    }
+
+    let (first, last) = match (price_path.first(), price_path.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return HedgeResult::default(),
+    };
+    let position_pnl = position.value(last) - position.value(first);
+    let hedge_pnl = squeeth_pnl + delta_hedge_pnl;
+    HedgeResult {
+        position_pnl,
+        hedge_pnl,
+        transaction_cost: 0.0,
+        net_pnl: position_pnl + hedge_pnl,
+    }
+}
+
+// Value of the contract at a spot with 't' years left, its payoff once expired
+fn contract_value(contract: &OptionContract, s: f64, t: f64) -> f64 {
+    if t <= 0.0 {
+        return match contract.kind {
+            OptionType::Call => call_at_expiry(s, contract.x),
+            OptionType::Put => put_at_expiry(s, contract.x),
+        };
+    }
+    OptionContract {
+        s0: s,
+        t,
+        ..*contract
+    }
+    .price()
+}
+
+// Delta of the contract at a spot with 't' years left, that of its payoff once expired
+fn contract_delta(contract: &OptionContract, s: f64, t: f64) -> f64 {
+    let (x, r, q, sigma) = (contract.x, contract.r, contract.q, contract.sigma);
+    match contract.kind {
+        OptionType::Call if t <= 0.0 => {
+            if s > x {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        OptionType::Put if t <= 0.0 => {
+            if s < x {
+                -1.0
+            } else {
+                0.0
+            }
+        }
+        _ => delta(contract.kind, s, x, t, r, q, sigma),
+    }
+}
+
+/// Simulates a long option delta hedged in the underlying along a price path, with transaction costs
+///
+/// The hedge is set to the Black-Scholes delta at each price but the last, including the initial trade, with the
+/// contract's time to expiry running down by `dt` each step. Past expiry the option is held at its payoff, so a path
+/// longer than `t / dt` is hedged with the payoff's delta, one (minus one for a put) in the money and zero out of it.
+/// Every trade costs `transaction_cost_bps` basis points of its notional, `cost_bps / 10000 * |delta_change| * s`.
+/// Financing of the premium and the hedge is ignored.
+///
+/// # Arguments
+/// * `contract` - The option held, its spot is replaced by the path
+/// * `price_path` - Underlying prices at each rebalance, starting at the entry price
+/// * `dt` - Time between prices as a percentage of the year
+/// * `transaction_cost_bps` - cost of trading the underlying in basis points of notional
+pub fn simulate_delta_hedge(
+    contract: &OptionContract,
+    price_path: &[f64],
+    dt: f64,
+    transaction_cost_bps: f64,
+) -> HedgeResult {
+    let (mut hedge_pnl, mut transaction_cost, mut held) = (0.0, 0.0, 0.0);
+    for (i, step) in price_path.windows(2).enumerate() {
+        let (s0, s1) = (step[0], step[1]);
+        let target = -contract_delta(contract, s0, contract.t - i as f64 * dt);
+        transaction_cost += transaction_cost_bps / 10_000.0 * (target - held).abs() * s0;
+        held = target;
+        hedge_pnl += held * (s1 - s0);
+    }
+
+    let (first, last) = match (price_path.first(), price_path.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return HedgeResult::default(),
+    };
+    let remaining = contract.t - (price_path.len() - 1) as f64 * dt;
+    let position_pnl =
+        contract_value(contract, last, remaining) - contract_value(contract, first, contract.t);
+    HedgeResult {
+        position_pnl,
+        hedge_pnl,
+        transaction_cost,
+        net_pnl: position_pnl + hedge_pnl - transaction_cost,
+    }
+}
+
+/// Calculates the Whalley-Wilmott half width of the no-trade band around the Black-Scholes delta
+///
+/// With proportional costs the optimal hedger only trades when the hedge drifts more than
+/// `(3 * e^(-rt) * cost * s0 * gamma^2 / (2 * risk_aversion))^(1/3)` from delta, and then only back to the edge
+/// of the band. The band widens with costs and gamma and narrows as the hedger grows more risk averse.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `risk_aversion` - exponential utility risk aversion of the hedger
+/// * `cost_bps` - cost of trading the underlying in basis points of notional
+#[allow(clippy::too_many_arguments)]
+pub fn rehedge_band(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    risk_aversion: f64,
+    cost_bps: f64,
+) -> f64 {
+    let cost = cost_bps / 10_000.0;
+    let gamma = gamma(s0, x, t, r, q, sigma);
+    (3.0 * (-r * t).exp() * cost * s0 * gamma.powf(2.0) / (2.0 * risk_aversion)).powf(1.0 / 3.0)
+}
+
+/// Builds the vanilla options that statically replicate a knock-out option by put-call symmetry
+///
+/// A call knocked out at a barrier below its strike (`down_and_out_call`) is long the call and short `x / barrier`
+/// puts struck at `pcs_equivalent_strike(barrier, x)`. If the barrier is touched the two legs are worth the same
+/// and the hedge is unwound for nothing, otherwise the puts expire worthless. A put knocked out above its strike
+/// (`up_and_out_put`) mirrors this with calls. The replication is exact when the underlying has no drift, `r == q`,
+/// and approximate otherwise.
+///
+/// # Arguments
+/// * `contract` - The vanilla option that the barrier knocks out
+/// * `barrier` - The knock-out level, below the strike for a call and above it for a put
+///
+/// # Panics
+/// If `barrier` is not positive, or is on the wrong side of the strike for the kind of option, as in
+/// `down_and_out_call` and `up_and_out_put`
+pub fn barrier_static_hedge(contract: &OptionContract, barrier: f64) -> Vec<Position> {
+    assert!(barrier > 0.0, "barrier = {} must be positive", barrier);
+    let mirror = match contract.kind {
+        OptionType::Call => {
+            assert!(
+                barrier <= contract.x,
+                "barrier = {} must be at or below the strike {}",
+                barrier,
+                contract.x
+            );
+            OptionType::Put
+        }
+        OptionType::Put => {
+            assert!(
+                barrier >= contract.x,
+                "barrier = {} must be at or above the strike {}",
+                barrier,
+                contract.x
+            );
+            OptionType::Call
+        }
+    };
+    vec![
+        Position {
+            kind: contract.kind,
+            x: contract.x,
+            quantity: 1.0,
+        },
+        Position {
+            kind: mirror,
+            x: pcs_equivalent_strike(barrier, contract.x),
+            quantity: -contract.x / barrier,
+        },
+    ]
+}
+
+/// Calculates how many of each out-of-the-money option replicate one unit of variance notional of a variance swap
+///
+/// The log contract that pays realized variance is replicated by puts below the forward and calls above it, each
+/// held in proportion to `1 / K^2`. On a discrete strike ladder strike `K_i` is held `2 * dK_i / (t * K_i^2)` times,
+/// where `dK_i` is half the distance between its neighbours (the full distance to the one neighbour at either end).
+/// The `1 / K^2` weighting is what keeps the vega of the strip independent of the underlying price. See
+/// `var_swap_strip` for the options themselves.
+///
+/// # Arguments
+/// * `strikes` - The strikes of the options, in increasing order
+/// * `t` - time to expiration as a percentage of the year
+///
+/// # Panics
+/// If there are fewer than two strikes
+pub fn var_swap_vega_weights(strikes: &[f64], t: f64) -> Vec<f64> {
+    assert!(
+        strikes.len() >= 2,
+        "a variance swap strip needs at least two strikes, got {}",
+        strikes.len()
+    );
+    let last = strikes.len() - 1;
+    (0..=last)
+        .map(|i| {
+            let dk = match i {
+                0 => strikes[1] - strikes[0],
+                i if i == last => strikes[last] - strikes[last - 1],
+                i => (strikes[i + 1] - strikes[i - 1]) / 2.0,
+            };
+            2.0 * dk / (t * strikes[i].powf(2.0))
+        })
+        .collect()
+}
+
+/// Builds the strip of out-of-the-money options which replicates one unit of variance notional of a variance swap
+///
+/// Strikes below the forward are puts and the rest calls, each held `var_swap_vega_weights` times.
+///
+/// # Arguments
+/// * `strikes` - The strikes of the options, in increasing order
+/// * `forward` - The forward price of the underlying at expiry, below which the options are puts
+/// * `t` - time to expiration as a percentage of the year
+///
+/// # Panics
+/// If there are fewer than two strikes or `forward` is not positive
+pub fn var_swap_strip(strikes: &[f64], forward: f64, t: f64) -> Vec<Position> {
+    assert!(forward > 0.0, "forward = {} must be positive", forward);
+    strikes
+        .iter()
+        .zip(var_swap_vega_weights(strikes, t))
+        .map(|(&x, quantity)| Position {
+            kind: if x < forward {
+                OptionType::Put
+            } else {
+                OptionType::Call
+            },
+            x,
+            quantity,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use contract::OptionContract;
+    use greeks::*;
+    use hedge::*;
+    use portfolio::Position;
+    use price::{down_and_out_call, euro_call, euro_put, up_and_out_put};
+
+    // base token (ETH) reserves
+    const BASE_RESERVE: f64 = 1.448;
+    // quote token (USDC) reserves
+    const QUOTE_RESERVE: f64 = 6779.0;
+    const P_A: f64 = 3747.0;
+    const P_B: f64 = 5024.0;
+    const P: f64 = 4360.61;
+    const NORMALIZATION_FACTOR: f64 = 0.8;
+    const IV: f64 = 0.9;
+    const DT: f64 = 1.0 / (365.0 * 24.0);
+
+    fn gamma_matched_units(position: &ClPosition) -> f64 {
+        position.gamma(P) / sqth_gamma(NORMALIZATION_FACTOR, IV)
+    }
+
+    // squeeth funding paid over a day at a flat price
+    fn one_day_funding(units: f64) -> f64 {
+        let decayed = sqth_norm_factor_decay(NORMALIZATION_FACTOR, IV, 24.0 * DT);
+        units * (sqth_to_usd(P, NORMALIZATION_FACTOR, IV) - sqth_to_usd(P, decayed, IV))
+    }
+
+    #[test]
+    fn test_simulate_lp_squeeth_hedge_flat() {
+        let position = ClPosition::new(P_A, P_B, BASE_RESERVE, QUOTE_RESERVE);
+        let units = gamma_matched_units(&position);
+        let path = vec![P; 25];
+        let result =
+            simulate_lp_squeeth_hedge(&position, &path, units, NORMALIZATION_FACTOR, IV, DT);
+        assert_eq!(result.position_pnl, 0.0);
+        // only the day of squeeth funding is lost
+        assert!((result.net_pnl + one_day_funding(units)).abs() < 1e-6);
+        assert!(result.net_pnl.abs() < 0.01 * position.value(P));
+    }
+
+    #[test]
+    fn test_simulate_lp_squeeth_hedge_move() {
+        let position = ClPosition::new(P_A, P_B, BASE_RESERVE, QUOTE_RESERVE);
+        let units = gamma_matched_units(&position);
+        // an hourly path swinging 1.5% either way, realizing far more than the squeeth implied vol
+        let path: Vec<f64> = (0..25)
+            .map(|i| if i % 2 == 0 { P } else { P * 1.015 })
+            .collect();
+        let unhedged =
+            simulate_lp_squeeth_hedge(&position, &path, 0.0, NORMALIZATION_FACTOR, IV, DT);
+        let hedged =
+            simulate_lp_squeeth_hedge(&position, &path, units, NORMALIZATION_FACTOR, IV, DT);
+        assert_eq!(hedged.position_pnl, unhedged.position_pnl);
+        // delta hedging alone leaves the gamma loss, squeeth offsets it at the cost of funding
+        let funding = one_day_funding(units);
+        assert!(unhedged.net_pnl < -funding);
+        assert!((hedged.net_pnl + funding).abs() < 0.1 * unhedged.net_pnl.abs());
+    }
+
+    // an hourly path over ten days that oscillates around the strike
+    fn option_path() -> Vec<f64> {
+        (0..=240)
+            .map(|i| 100.0 * (1.0 + 0.03 * (i as f64 / 5.0).sin() + 0.02 * (i as f64 / 37.0).sin()))
+            .collect()
+    }
+
+    #[test]
+    fn test_simulate_delta_hedge() {
+        let contract = OptionContract::new(OptionType::Call, 100.0, 100.0, 0.25, 0.0, 0.0, 0.5);
+        let path = option_path();
+        let free = simulate_delta_hedge(&contract, &path, DT, 0.0);
+        assert_eq!(free.transaction_cost, 0.0);
+        assert!((free.net_pnl - (free.position_pnl + free.hedge_pnl)).abs() < 1e-12);
+        // the hedge offsets most of the option's move
+        assert!(free.net_pnl.abs() < 0.2 * free.position_pnl.abs());
+
+        let costly = simulate_delta_hedge(&contract, &path, DT, 10.0);
+        assert!(costly.transaction_cost > 0.0);
+        assert!((free.net_pnl - costly.net_pnl - costly.transaction_cost).abs() < 1e-12);
+
+        // higher costs and more frequent rebalancing both drag harder
+        let costlier = simulate_delta_hedge(&contract, &path, DT, 20.0);
+        assert!(costlier.transaction_cost > costly.transaction_cost);
+        let daily: Vec<f64> = path.iter().step_by(24).cloned().collect();
+        let daily_costly = simulate_delta_hedge(&contract, &daily, 24.0 * DT, 10.0);
+        assert!(costly.transaction_cost > daily_costly.transaction_cost);
+    }
+
+    #[test]
+    fn test_simulate_delta_hedge_past_expiry() {
+        // the path runs twice as long as the option, which expires in the money
+        let contract = OptionContract::new(OptionType::Call, 100.0, 90.0, 0.25, 0.0, 0.0, 0.5);
+        let steps = 2.0 * contract.t / DT;
+        let path: Vec<f64> = (0..=steps as usize)
+            .map(|i| 100.0 + 10.0 * i as f64 / steps)
+            .collect();
+        let result = simulate_delta_hedge(&contract, &path, DT, 0.0);
+        assert!(result.net_pnl.is_finite() && result.hedge_pnl.is_finite());
+
+        // after expiry the short underlying offsets the in the money payoff one for one
+        let expiry = (steps / 2.0) as usize;
+        let expired = OptionContract {
+            s0: path[expiry],
+            t: 0.0,
+            ..contract
+        };
+        let tail = simulate_delta_hedge(&expired, &path[expiry..], DT, 0.0);
+        assert!((tail.hedge_pnl + (path[path.len() - 1] - path[expiry])).abs() < 1e-9);
+        assert!(tail.net_pnl.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rehedge_band() {
+        let band = |risk_aversion: f64, cost_bps: f64| {
+            rehedge_band(
+                64.68,
+                65.0,
+                23.0 / 365.0,
+                0.015,
+                0.021,
+                0.5051,
+                risk_aversion,
+                cost_bps,
+            )
+        };
+        assert!(band(1.0, 10.0) > 0.0);
+        assert_eq!(band(1.0, 0.0), 0.0);
+        // higher costs widen the band, by the cube root of the cost
+        assert!(band(1.0, 20.0) > band(1.0, 10.0));
+        assert!((band(1.0, 80.0) / band(1.0, 10.0) - 2.0).abs() < 1e-12);
+        // a more risk averse hedger trades sooner
+        assert!(band(2.0, 10.0) < band(1.0, 10.0));
+    }
+
+    // value of a set of vanilla positions on one underlying
+    fn book_value(legs: &[Position], contract: &OptionContract, s0: f64, t: f64) -> f64 {
+        legs.iter()
+            .map(|leg| {
+                let option = OptionContract {
+                    kind: leg.kind,
+                    x: leg.x,
+                    ..*contract
+                };
+                leg.quantity * contract_value(&option, s0, t)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_barrier_static_hedge() {
+        // no drift, r == q, where the symmetry hedge is exact
+        let (s0, x, t, rate, vol) = (100.0, 100.0, 0.5, 0.03, 0.25);
+        let call = OptionContract::new(OptionType::Call, s0, x, t, rate, rate, vol);
+        let hedge = barrier_static_hedge(&call, 90.0);
+        assert_eq!(hedge.len(), 2);
+        assert_eq!(hedge[1].kind, OptionType::Put);
+        assert!((hedge[1].x - 81.0).abs() < 1e-12);
+
+        // the hedge costs what the barrier option does at inception
+        let barrier = down_and_out_call(s0, x, 90.0, t, rate, rate, vol);
+        assert!((book_value(&hedge, &call, s0, t) - barrier).abs() < 1e-6);
+        // and can be unwound for nothing if the barrier is touched later on
+        assert!(book_value(&hedge, &call, 90.0, 0.2).abs() < 1e-6);
+        // while the puts expire worthless if it never is
+        assert_eq!(book_value(&hedge, &call, 110.0, 0.0), 10.0);
+
+        let put = OptionContract::new(OptionType::Put, s0, x, t, rate, rate, vol);
+        let hedge = barrier_static_hedge(&put, 120.0);
+        let barrier = up_and_out_put(s0, x, 120.0, t, rate, rate, vol);
+        assert!((book_value(&hedge, &put, s0, t) - barrier).abs() < 1e-6);
+        assert!(book_value(&hedge, &put, 120.0, 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "barrier = 110 must be at or below the strike 100")]
+    fn test_barrier_static_hedge_call_barrier_above_strike() {
+        let call = OptionContract::new(OptionType::Call, 100.0, 100.0, 0.5, 0.03, 0.03, 0.25);
+        barrier_static_hedge(&call, 110.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "barrier = 90 must be at or above the strike 100")]
+    fn test_barrier_static_hedge_put_barrier_below_strike() {
+        let put = OptionContract::new(OptionType::Put, 100.0, 100.0, 0.5, 0.03, 0.03, 0.25);
+        barrier_static_hedge(&put, 90.0);
+    }
+
+    #[test]
+    fn test_var_swap_vega_weights() {
+        let strikes: Vec<f64> = (1..=400).map(|i| i as f64).collect();
+        let (forward, t, vol) = (100.0, 0.25, 0.4);
+        let weights = var_swap_vega_weights(&strikes, t);
+        // evenly spaced strikes are held in proportion to 1 / K^2
+        for i in 1..strikes.len() - 1 {
+            let scaled = weights[i] * strikes[i].powf(2.0);
+            assert!((scaled - 2.0 / t).abs() < 1e-12);
+        }
+        assert!((weights[49] / weights[99] - 4.0).abs() < 1e-12);
+
+        // puts below the forward and calls from it up, held by weight
+        let positions = var_swap_strip(&strikes, forward, t);
+        assert_eq!(positions[98].kind, OptionType::Put);
+        assert_eq!(positions[99].kind, OptionType::Call);
+        assert!(positions
+            .iter()
+            .zip(weights.iter())
+            .all(|(p, &w)| p.quantity == w));
+
+        // with no carry the out-of-the-money strip prices the variance, and its vega barely moves with spot
+        let strip = |s0: f64, sigma: f64| -> f64 {
+            positions
+                .iter()
+                .map(|p| {
+                    let price = match p.kind {
+                        OptionType::Put => euro_put(s0, p.x, t, 0.0, 0.0, sigma),
+                        OptionType::Call => euro_call(s0, p.x, t, 0.0, 0.0, sigma),
+                    };
+                    p.quantity * price
+                })
+                .sum()
+        };
+        assert!((strip(forward, vol) - vol * vol).abs() < 1e-3 * vol * vol);
+        let vega_at = |s0: f64| (strip(s0, vol + 1e-4) - strip(s0, vol - 1e-4)) / 2e-4;
+        for &s0 in [85.0, 115.0].iter() {
+            assert!((vega_at(s0) - vega_at(forward)).abs() < 0.01 * vega_at(forward));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a variance swap strip needs at least two strikes, got 1")]
+    fn test_var_swap_vega_weights_one_strike() {
+        var_swap_vega_weights(&[100.0], 0.25);
+    }
+}
@@ -0,0 +1,516 @@
+// Module containing implied volatility solvers
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use std::f64::consts::{E, PI};
+
+use common::OptionType;
+use greeks::vega;
+use price::euro_call;
+use solvers::{bisect, brent, newton};
+
+// Volatility bracket the Newton solver must stay in before falling back to bisection
+const MIN_VOL: f64 = 1e-4;
+const MAX_VOL: f64 = 5.0;
+
+/// A quoted option on a chain sharing one underlying
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChainQuote {
+    pub kind: OptionType,
+    /// The strike price of the option
+    pub x: f64,
+    /// time to expiration as a percentage of the year
+    pub t: f64,
+    /// quoted price of the option
+    pub price: f64,
+}
+
+/// Solves for the volatility at which the Black-Scholes price matches a quoted price
+///
+/// Runs Newton-Raphson on the price using vega, returning `None` if it fails to converge. The search starts from
+/// the Manaster-Koehler or Brenner-Subrahmanyam estimate rather than a fixed volatility, so deep in or out of the
+/// money quotes converge too. When Newton stalls or leaves volatilities between 0.01% and 500% it falls back to
+/// bisecting that bracket.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `price` - quoted price of the option
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+pub fn implied_vol(
+    kind: OptionType,
+    price: f64,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+) -> Option<f64> {
+    // non-positive volatilities and vanishing vega end the search
+    let diff = |sigma: f64| {
+        if sigma <= 0.0 {
+            return f64::NAN;
+        }
+        ::price::price(kind, s0, x, t, r, q, sigma) - price
+    };
+    let slope = |sigma: f64| {
+        // vega is quoted per volatility point
+        let slope = 100.0 * vega(s0, x, t, r, q, sigma);
+        if slope < 1e-12 {
+            f64::NAN
+        } else {
+            slope
+        }
+    };
+    match newton(diff, slope, newton_seed(price, s0, x, t, r, q), 1e-8, 100) {
+        Some(sigma) if (MIN_VOL..=MAX_VOL).contains(&sigma) => Some(sigma),
+        _ => implied_vol_bisection(kind, price, s0, x, t, r, q, MIN_VOL, MAX_VOL),
+    }
+}
+
+/// Solves for the volatility at which the Black-Scholes price of a call matches a quoted price by bisection
+///
+/// Slower than `implied_vol_call` but immune to vanishing vega, e.g. near expiry or deep in the money. Bisects
+/// `[lo, hi]` to machine precision, well inside a price gap of 1e-8, and returns `None` when the bracket does not
+/// contain the quote. A bracket of 1e-4 to 5.0 covers any market.
+///
+/// # Arguments
+/// * `price` - quoted price of the option
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `lo` - lowest volatility searched
+/// * `hi` - highest volatility searched
+#[allow(clippy::too_many_arguments)]
+pub fn implied_vol_bisection_call(
+    price: f64,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    lo: f64,
+    hi: f64,
+) -> Option<f64> {
+    implied_vol_bisection(OptionType::Call, price, s0, x, t, r, q, lo, hi)
+}
+
+/// Solves for the volatility at which the Black-Scholes price of a put matches a quoted price by bisection
+///
+/// Slower than `implied_vol_put` but immune to vanishing vega, e.g. near expiry or deep in the money. Bisects
+/// `[lo, hi]` to machine precision, well inside a price gap of 1e-8, and returns `None` when the bracket does not
+/// contain the quote. A bracket of 1e-4 to 5.0 covers any market.
+///
+/// # Arguments
+/// * `price` - quoted price of the option
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `lo` - lowest volatility searched
+/// * `hi` - highest volatility searched
+#[allow(clippy::too_many_arguments)]
+pub fn implied_vol_bisection_put(
+    price: f64,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    lo: f64,
+    hi: f64,
+) -> Option<f64> {
+    implied_vol_bisection(OptionType::Put, price, s0, x, t, r, q, lo, hi)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn implied_vol_bisection(
+    kind: OptionType,
+    price: f64,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    lo: f64,
+    hi: f64,
+) -> Option<f64> {
+    let diff = |sigma: f64| ::price::price(kind, s0, x, t, r, q, sigma) - price;
+    bisect(diff, lo, hi, 0.0)
+}
+
+// Starting volatility for Newton: the inflection point of the price in volatility (Manaster-Koehler), from which
+// the iteration converges, or the Brenner-Subrahmanyam at-the-money approximation where that point is near zero
+fn newton_seed(price: f64, s0: f64, x: f64, t: f64, r: f64, q: f64) -> f64 {
+    let forward = s0 * E.powf((r - q) * t);
+    let inflection = (2.0 * (forward / x).ln().abs() / t).sqrt();
+    let at_the_money = (2.0 * PI / t).sqrt() * price / s0;
+    inflection.max(at_the_money)
+}
+
+/// Solves for the volatility at which the Black-Scholes price of a call matches a quoted price
+///
+/// Returns `None` for quotes outside the no-arbitrage bounds `[max(s0 * e^(-qt) - x * e^(-rt), 0), s0 * e^(-qt)]`,
+/// which no volatility reproduces, and otherwise runs `implied_vol`.
+///
+/// # Arguments
+/// * `price` - quoted price of the option
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+pub fn implied_vol_call(price: f64, s0: f64, x: f64, t: f64, r: f64, q: f64) -> Option<f64> {
+    let asset = s0 * E.powf(-q * t);
+    let cash = x * E.powf(-r * t);
+    if price <= (asset - cash).max(0.0) || price >= asset {
+        return None;
+    }
+    implied_vol(OptionType::Call, price, s0, x, t, r, q)
+}
+
+/// Solves for the volatility at which the Black-Scholes price of a put matches a quoted price
+///
+/// Returns `None` for quotes outside the no-arbitrage bounds `[max(x * e^(-rt) - s0 * e^(-qt), 0), x * e^(-rt)]`,
+/// which no volatility reproduces, and otherwise runs `implied_vol`.
+///
+/// # Arguments
+/// * `price` - quoted price of the option
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+pub fn implied_vol_put(price: f64, s0: f64, x: f64, t: f64, r: f64, q: f64) -> Option<f64> {
+    let asset = s0 * E.powf(-q * t);
+    let cash = x * E.powf(-r * t);
+    if price <= (cash - asset).max(0.0) || price >= cash {
+        return None;
+    }
+    implied_vol(OptionType::Put, price, s0, x, t, r, q)
+}
+
+/// Finds the lowest volatility consistent with a quoted price, or `None` if the price is an arbitrage
+///
+/// As volatility falls to zero a European option is worth its discounted intrinsic value
+/// `max(s0 * e^(-qt) - x * e^(-rt), 0)` for a call (and the mirror image for a put). A quote below that bound
+/// is a hard arbitrage. A quote at the bound implies zero volatility, and above it the Black-Scholes price
+/// rises with volatility so the floor is the volatility at which the model matches the quote, found by bisection.
+///
+/// # Arguments
+/// * `price` - quoted price of the option
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+pub fn min_arbitrage_free_vol(
+    price: f64,
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+) -> Option<f64> {
+    let forward_intrinsic = s0 * E.powf(-q * t) - x * E.powf(-r * t);
+    let lower_bound = match kind {
+        OptionType::Call => forward_intrinsic.max(0.0),
+        OptionType::Put => (-forward_intrinsic).max(0.0),
+    };
+    let tolerance = 1e-10 * s0.max(x);
+    if price < lower_bound - tolerance {
+        return None;
+    }
+    if price <= lower_bound + tolerance {
+        return Some(0.0);
+    }
+    let diff = |sigma: f64| ::price::price(kind, s0, x, t, r, q, sigma) - price;
+    // start just above zero, where d1 is undefined at the forward
+    bisect(diff, 1e-8, 10.0, 0.0)
+}
+
+/// Calculates the far expiry volatility at which a call calendar spread costs nothing
+///
+/// Solves `euro_call(t2, sigma2) = euro_call(t1, sigma1)` for `sigma2`, the lowest far volatility that does not
+/// make the calendar a free option. Together with `sigma1` it implies a `forward_vol` between the expiries, which is
+/// the forward volatility the calendar breaks even at. Returns `NaN` when even a volatility of 1000% leaves the far
+/// call cheaper than the near one, or when carry alone makes it dearer.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `x` - The strike price of the options
+/// * `t1` - time to the near expiry as a percentage of the year
+/// * `t2` - time to the far expiry as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma1` - volatility of the near expiry
+pub fn calendar_breakeven_vol(
+    s0: f64,
+    x: f64,
+    t1: f64,
+    t2: f64,
+    r: f64,
+    q: f64,
+    sigma1: f64,
+) -> f64 {
+    let near = euro_call(s0, x, t1, r, q, sigma1);
+    let spread = |sigma2: f64| euro_call(s0, x, t2, r, q, sigma2) - near;
+    brent(spread, 1e-8, 10.0, 1e-12).unwrap_or(f64::NAN)
+}
+
+/// Calculates the change in implied volatility per unit change in the option price
+///
+/// This is `1 / vega` with vega taken per unit of volatility rather than per point, so a price error of `dp`
+/// moves the implied volatility by roughly `dp * vol_per_price`. Returns `NaN` when vega is too small for the
+/// implied volatility to be pinned down by the price, e.g. far from the money close to expiry.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn vol_per_price(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let vega_raw = 100.0 * vega(s0, x, t, r, q, sigma);
+    if vega_raw < 1e-12 {
+        return f64::NAN;
+    }
+    1.0 / vega_raw
+}
+
+/// Solves the implied volatility of every quote on a chain
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `quotes` - quoted options on the chain
+pub fn implied_vols_chain(s0: f64, r: f64, q: f64, quotes: &[ChainQuote]) -> Vec<Option<f64>> {
+    quotes
+        .iter()
+        .map(|quote| implied_vol(quote.kind, quote.price, s0, quote.x, quote.t, r, q))
+        .collect()
+}
+
+/// Solves the implied volatility of every quote on a chain in parallel
+///
+/// Uses rayon when the `rayon` feature is enabled and otherwise falls back to `implied_vols_chain`.
+/// Both give identical results.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `quotes` - quoted options on the chain
+#[cfg(feature = "rayon")]
+pub fn implied_vols_chain_par(s0: f64, r: f64, q: f64, quotes: &[ChainQuote]) -> Vec<Option<f64>> {
+    quotes
+        .par_iter()
+        .map(|quote| implied_vol(quote.kind, quote.price, s0, quote.x, quote.t, r, q))
+        .collect()
+}
+
+/// Solves the implied volatility of every quote on a chain in parallel
+///
+/// Uses rayon when the `rayon` feature is enabled and otherwise falls back to `implied_vols_chain`.
+/// Both give identical results.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the options
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `quotes` - quoted options on the chain
+#[cfg(not(feature = "rayon"))]
+pub fn implied_vols_chain_par(s0: f64, r: f64, q: f64, quotes: &[ChainQuote]) -> Vec<Option<f64>> {
+    implied_vols_chain(s0, r, q, quotes)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use iv::*;
+    use models::forward_vol;
+    use price::{euro_call, euro_put};
+    use std::f64::consts::E;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_calendar_breakeven_vol() {
+        let p = SAMPLE_PARAMS;
+        let (t1, t2) = (p.t, 3.0 * p.t);
+        let sigma2 = calendar_breakeven_vol(p.s0, p.x, t1, t2, p.r, p.q, p.sigma);
+        let near = euro_call(p.s0, p.x, t1, p.r, p.q, p.sigma);
+        let far = euro_call(p.s0, p.x, t2, p.r, p.q, sigma2);
+        assert!((far - near).abs() < 1e-9);
+        assert!(sigma2 < p.sigma);
+
+        // at the money forward without carry the price depends only on total variance, so a zero cost calendar
+        // holds no variance between the expiries
+        let flat = calendar_breakeven_vol(p.s0, p.s0, t1, t2, 0.0, 0.0, p.sigma);
+        assert!((flat.powf(2.0) * t2 - p.sigma.powf(2.0) * t1).abs() < 1e-9);
+        let forward = forward_vol(p.sigma, t1, flat + 1e-6, t2);
+        assert!(forward < 0.01);
+    }
+
+    #[test]
+    fn test_min_arbitrage_free_vol() {
+        let p = SAMPLE_PARAMS;
+        let intrinsic = p.s0 * E.powf(-p.q * p.t) - 55.0 * E.powf(-p.r * p.t);
+        let floor =
+            |price: f64| min_arbitrage_free_vol(price, OptionType::Call, p.s0, 55.0, p.t, p.r, p.q);
+        assert_eq!(floor(intrinsic), Some(0.0));
+        assert_eq!(floor(intrinsic - 0.01), None);
+
+        let price = euro_call(p.s0, 55.0, p.t, p.r, p.q, p.sigma);
+        assert!((floor(price).unwrap() - p.sigma).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_min_arbitrage_free_vol_put() {
+        let p = SAMPLE_PARAMS;
+        // out of the money the bound is zero, so any negative quote is an arbitrage
+        let floor = min_arbitrage_free_vol(-0.01, OptionType::Put, p.s0, p.x, p.t, p.r, p.q);
+        assert_eq!(floor, None);
+    }
+
+    #[test]
+    fn test_vol_per_price() {
+        let p = SAMPLE_PARAMS;
+        let sensitivity = vol_per_price(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        // a one cent price error shifts the implied vol by one cent of sensitivity
+        let price = euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let shifted =
+            implied_vol(OptionType::Call, price + 0.01, p.s0, p.x, p.t, p.r, p.q).unwrap();
+        let abs = (shifted - p.sigma - 0.01 * sensitivity).abs();
+        assert!(abs < 0.00001);
+    }
+
+    #[test]
+    fn test_vol_per_price_vanishing_vega() {
+        let p = SAMPLE_PARAMS;
+        let sensitivity = vol_per_price(p.s0, 4.0 * p.x, 1.0 / p.days_per_year, p.r, p.q, p.sigma);
+        assert!(sensitivity.is_nan());
+    }
+
+    fn synthetic_chain() -> Vec<ChainQuote> {
+        let p = SAMPLE_PARAMS;
+        let mut quotes = Vec::new();
+        for i in 0..2000 {
+            let x = p.x * (0.7 + 0.6 * (i % 100) as f64 / 100.0);
+            let t = p.t * (1 + i / 100) as f64;
+            let sigma = p.sigma + 0.2 * ((i % 7) as f64 / 7.0 - 0.5);
+            let (kind, price) = if i % 2 == 0 {
+                let price = euro_call(p.s0, x, t, p.r, p.q, sigma);
+                (OptionType::Call, price)
+            } else {
+                let price = euro_put(p.s0, x, t, p.r, p.q, sigma);
+                (OptionType::Put, price)
+            };
+            quotes.push(ChainQuote { kind, x, t, price });
+        }
+        quotes
+    }
+
+    #[test]
+    fn test_implied_vol() {
+        let p = SAMPLE_PARAMS;
+        let price = euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let sigma = implied_vol(OptionType::Call, price, p.s0, p.x, p.t, p.r, p.q).unwrap();
+        assert!((sigma - p.sigma).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_implied_vol_call_put_round_trip() {
+        let p = SAMPLE_PARAMS;
+        let (s, t, r, q) = (p.s0, p.t, p.r, p.q);
+        for &x in [40.0, 55.0, p.x, 75.0, 100.0].iter() {
+            for &sigma in [0.05, 0.2, p.sigma, 1.2, 3.0].iter() {
+                // skip quotes a price tolerance of 1e-8 cannot pin to a volatility
+                if vega(s, x, t, r, q, sigma) < 1e-4 {
+                    continue;
+                }
+                let call = euro_call(s, x, t, r, q, sigma);
+                let put = euro_put(s, x, t, r, q, sigma);
+                assert!((implied_vol_call(call, s, x, t, r, q).unwrap() - sigma).abs() < 1e-4);
+                assert!((implied_vol_put(put, s, x, t, r, q).unwrap() - sigma).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_implied_vol_call_put_arbitrage() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q) = (p.s0, p.x, p.t, p.r, p.q);
+        // below the discounted intrinsic value, and above the discounted asset or strike
+        assert_eq!(implied_vol_call(0.0, s, x, t, r, q), None);
+        assert_eq!(implied_vol_call(s, s, x, t, r, q), None);
+        assert_eq!(implied_vol_put(-1.0, s, x, t, r, q), None);
+        assert_eq!(implied_vol_put(x, s, x, t, r, q), None);
+        assert_eq!(implied_vol_put(20.0, s, 90.0, t, r, q), None);
+    }
+
+    #[test]
+    fn test_implied_vol_bisection_near_expiry() {
+        let p = SAMPLE_PARAMS;
+        // a day to expiry and deep enough in the money that the call is worth 1.6e-7 over its intrinsic value
+        let (s, x, t, r, q, sigma) = (p.s0, 60.0, 1.0 / 365.0, p.r, p.q, 0.3);
+        let call = euro_call(s, x, t, r, q, sigma);
+        let put = euro_put(s, x, t, r, q, sigma);
+
+        // Newton from a flat 20% seed finds no vega to follow
+        let diff = |v: f64| euro_call(s, x, t, r, q, v) - call;
+        let slope = |v: f64| 100.0 * vega(s, x, t, r, q, v);
+        assert_eq!(newton(diff, slope, 0.2, 1e-8, 100), None);
+
+        let recovered = implied_vol_bisection_call(call, s, x, t, r, q, 1e-4, 5.0).unwrap();
+        assert!((recovered - sigma).abs() < 1e-6);
+        let recovered = implied_vol_bisection_put(put, s, x, t, r, q, 1e-4, 5.0).unwrap();
+        assert!((recovered - sigma).abs() < 1e-6);
+        assert!((implied_vol_call(call, s, x, t, r, q).unwrap() - sigma).abs() < 1e-3);
+
+        // a bracket that misses the volatility
+        assert_eq!(
+            implied_vol_bisection_call(call, s, x, t, r, q, 0.5, 5.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_implied_vol_falls_back_to_bisection() {
+        let p = SAMPLE_PARAMS;
+        // a week out, a put struck far above spot is nearly all intrinsic value and the at the money estimate
+        // seeds Newton far beyond any volatility the quote could carry
+        let (s, x, t, r, q, sigma) = (p.s0, 150.0, 7.0 / 365.0, p.r, p.q, 2.0);
+        let put = euro_put(s, x, t, r, q, sigma);
+        let diff = |v: f64| euro_put(s, x, t, r, q, v) - put;
+        let slope = |v: f64| 100.0 * vega(s, x, t, r, q, v);
+        let seed = newton_seed(put, s, x, t, r, q);
+        assert!(seed > MAX_VOL);
+        assert_eq!(newton(diff, slope, seed, 1e-8, 100), None);
+
+        let bisected = implied_vol_bisection_put(put, s, x, t, r, q, MIN_VOL, MAX_VOL).unwrap();
+        let recovered = implied_vol(OptionType::Put, put, s, x, t, r, q).unwrap();
+        assert_eq!(recovered, bisected);
+        assert!((recovered - sigma).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_implied_vols_chain_par_matches_serial() {
+        let p = SAMPLE_PARAMS;
+        let quotes = synthetic_chain();
+        let serial = implied_vols_chain(p.s0, p.r, p.q, &quotes);
+        let parallel = implied_vols_chain_par(p.s0, p.r, p.q, &quotes);
+        assert_eq!(serial, parallel);
+        assert!(serial.iter().any(|sigma| sigma.is_some()));
+    }
+}
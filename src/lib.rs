@@ -1,10 +1,53 @@
+extern crate rand_core;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+#[cfg(feature = "autodiff")]
+mod autodiff;
 mod common;
+mod contract;
+mod exposure;
+mod finite_diff;
+#[cfg(test)]
+mod fuzz;
 mod greeks;
+mod hedge;
+mod iv;
+mod live;
+mod mc;
+mod models;
+mod portfolio;
 mod price;
+mod probability;
+mod quote;
+mod solvers;
 mod stats;
+#[cfg(test)]
+mod test_fixtures;
+mod validation;
 mod value;
 
+#[cfg(feature = "autodiff")]
+pub use autodiff::*;
 pub use common::*;
+pub use contract::*;
+pub use exposure::*;
+pub use finite_diff::*;
 pub use greeks::*;
+pub use hedge::*;
+pub use iv::*;
+pub use live::*;
+pub use mc::*;
+pub use models::*;
+pub use portfolio::*;
 pub use price::*;
+pub use probability::*;
+pub use quote::*;
+pub use solvers::*;
+pub use stats::*;
+pub use validation::*;
 pub use value::*;
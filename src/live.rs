@@ -0,0 +1,165 @@
+// Module containing an option whose greeks are cheaply refreshed as the spot ticks
+use std::f64::consts::E;
+
+use common::*;
+use greeks::Greeks;
+use stats::{cnd, pdf};
+
+/// A European option holding its static inputs so that only spot dependent terms are recomputed on a tick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiveOption {
+    kind: OptionType,
+    x: f64,
+    t: f64,
+    sigma: f64,
+    r: f64,
+    q: f64,
+    days_per_year: f64,
+    // terms which do not depend on spot
+    sigma_sqrt_t: f64,
+    drift: f64,
+    asset_discount: f64,
+    cash_discount: f64,
+    // outputs at the current spot
+    s0: f64,
+    price: f64,
+    greeks: Greeks,
+}
+
+impl LiveOption {
+    /// Creates the option and computes its price and greeks at the initial spot
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `s0` - The underlying price of the option
+    /// * `x` - The strike price of the option
+    /// * `t` - time to expiration as a percentage of the year
+    /// * `r` - continuously compounded risk-free interest rate
+    /// * `q` - continuously compounded divident yield
+    /// * `sigma` - volatility
+    /// * `days_per_year` - the number of calendar days in the year
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        kind: OptionType,
+        s0: f64,
+        x: f64,
+        t: f64,
+        r: f64,
+        q: f64,
+        sigma: f64,
+        days_per_year: f64,
+    ) -> LiveOption {
+        let mut option = LiveOption {
+            kind,
+            x,
+            t,
+            sigma,
+            r,
+            q,
+            days_per_year,
+            sigma_sqrt_t: sigma * t.sqrt(),
+            drift: t * (r - q + sigma.powf(2.0) / 2.0),
+            asset_discount: E.powf(-q * t),
+            cash_discount: E.powf(-r * t),
+            s0,
+            price: 0.0,
+            greeks: Greeks::default(),
+        };
+        option.update_spot(s0);
+        option
+    }
+
+    /// Moves the spot and recomputes the price and greeks
+    ///
+    /// # Arguments
+    /// * `new_spot` - The new underlying price
+    pub fn update_spot(&mut self, new_spot: f64) {
+        let d1 = ((new_spot / self.x).ln() + self.drift) / self.sigma_sqrt_t;
+        let d2 = d1 - self.sigma_sqrt_t;
+        let density = pdf(d1);
+
+        let asset = new_spot * self.asset_discount;
+        let cash = self.x * self.cash_discount;
+        let time_decay = -asset * density * self.sigma / (2.0 * self.t.sqrt());
+        let (price, delta, carry, rho) = match self.kind {
+            OptionType::Call => (
+                asset * cnd(d1) - cash * cnd(d2),
+                self.asset_discount * cnd(d1),
+                self.q * asset * cnd(d1) - self.r * cash * cnd(d2),
+                self.t * cash * cnd(d2),
+            ),
+            OptionType::Put => (
+                cash * cnd(-d2) - asset * cnd(-d1),
+                self.asset_discount * (cnd(d1) - 1.0),
+                self.r * cash * cnd(-d2) - self.q * asset * cnd(-d1),
+                -self.t * cash * cnd(-d2),
+            ),
+        };
+
+        self.s0 = new_spot;
+        self.price = price.max(0.0);
+        self.greeks = Greeks {
+            delta,
+            gamma: self.asset_discount * density / (new_spot * self.sigma_sqrt_t),
+            theta: (time_decay + carry) / self.days_per_year,
+            vega: (1.0 / 100.0) * asset * self.t.sqrt() * density,
+            rho: (1.0 / 100.0) * rho,
+        };
+    }
+
+    /// The current underlying price
+    pub fn spot(&self) -> f64 {
+        self.s0
+    }
+
+    /// The option price at the current spot
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    /// The greeks at the current spot
+    pub fn greeks(&self) -> Greeks {
+        self.greeks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use greeks::*;
+    use live::*;
+    use price::*;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_update_spot_matches_fresh_computation() {
+        let p = SAMPLE_PARAMS;
+        for kind in [OptionType::Call, OptionType::Put].iter() {
+            let mut option =
+                LiveOption::new(*kind, p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
+            for spot in [60.0, 64.68, 71.3, 55.25, 66.0].iter() {
+                option.update_spot(*spot);
+                let expected = gbsm_greeks(
+                    *kind,
+                    *spot,
+                    p.x,
+                    p.t,
+                    p.r,
+                    p.r - p.q,
+                    p.sigma,
+                    p.days_per_year,
+                );
+                let price = gbsm_price(*kind, *spot, p.x, p.t, p.r, p.r - p.q, p.sigma);
+                let greeks = option.greeks();
+                assert_eq!(option.spot(), *spot);
+                assert!((option.price() - price).abs() < 1e-10);
+                assert!((greeks.delta - expected.delta).abs() < 1e-10);
+                assert!((greeks.gamma - expected.gamma).abs() < 1e-10);
+                assert!((greeks.theta - expected.theta).abs() < 1e-10);
+                assert!((greeks.vega - expected.vega).abs() < 1e-10);
+                assert!((greeks.rho - expected.rho).abs() < 1e-10);
+            }
+        }
+    }
+}
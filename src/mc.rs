@@ -0,0 +1,225 @@
+// Module containing Monte Carlo pricers
+use rand_core::{impls, Error, RngCore};
+use std::f64::consts::E;
+
+use common::forward_price;
+use stats::sample_standard_normal;
+
+/// Monte Carlo estimate of a price alongside its standard error
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct McResult {
+    pub price: f64,
+    pub std_err: f64,
+}
+
+// SplitMix64 generator so runs are reproducible from a seed, implementing `rand_core`'s `RngCore` without
+// pulling in a full rng crate. Shared by the crate's tests wherever they need random draws
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+// Terminal spot of a geometric Brownian motion given a standard normal draw 'z'
+fn terminal_spot(s0: f64, t: f64, r: f64, q: f64, sigma: f64, z: f64) -> f64 {
+    s0 * E.powf((r - q - sigma.powf(2.0) / 2.0) * t + sigma * t.sqrt() * z)
+}
+
+// Sample mean and standard error of the mean
+fn mean_std_err(samples: &[f64]) -> McResult {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|s| (s - mean).powf(2.0)).sum::<f64>() / (n - 1.0);
+    McResult {
+        price: mean,
+        std_err: (variance / n).sqrt(),
+    }
+}
+
+/// Prices a european call by plain Monte Carlo simulation of the terminal spot
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `paths` - number of simulated paths
+/// * `seed` - seed of the random number generator
+#[allow(clippy::too_many_arguments)]
+pub fn mc_euro_call(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    paths: usize,
+    seed: u64,
+) -> McResult {
+    let mut rng = SplitMix64(seed);
+    let discount = E.powf(-r * t);
+    let payoffs: Vec<f64> = (0..paths)
+        .map(|_| {
+            let s_t = terminal_spot(s0, t, r, q, sigma, sample_standard_normal(&mut rng));
+            discount * (s_t - x).max(0.0)
+        })
+        .collect();
+    mean_std_err(&payoffs)
+}
+
+/// Prices a european call by Monte Carlo using the terminal spot as a control variate
+///
+/// The terminal spot has a known risk-neutral expectation, the forward, so the payoff estimate is
+/// corrected by `beta * (mean(S_T) - F)` with `beta` the sample regression coefficient of payoff on `S_T`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `paths` - number of simulated paths
+/// * `seed` - seed of the random number generator
+#[allow(clippy::too_many_arguments)]
+pub fn mc_euro_call_control_variate(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    paths: usize,
+    seed: u64,
+) -> McResult {
+    let mut rng = SplitMix64(seed);
+    let discount = E.powf(-r * t);
+    let (spots, payoffs): (Vec<f64>, Vec<f64>) = (0..paths)
+        .map(|_| {
+            let s_t = terminal_spot(s0, t, r, q, sigma, sample_standard_normal(&mut rng));
+            (s_t, discount * (s_t - x).max(0.0))
+        })
+        .unzip();
+
+    let n = paths as f64;
+    let spot_mean = spots.iter().sum::<f64>() / n;
+    let payoff_mean = payoffs.iter().sum::<f64>() / n;
+    let covariance = spots
+        .iter()
+        .zip(payoffs.iter())
+        .map(|(s, p)| (s - spot_mean) * (p - payoff_mean))
+        .sum::<f64>();
+    let spot_variance = spots.iter().map(|s| (s - spot_mean).powf(2.0)).sum::<f64>();
+    let beta = covariance / spot_variance;
+
+    let forward = forward_price(s0, t, r, q);
+    let adjusted: Vec<f64> = spots
+        .iter()
+        .zip(payoffs.iter())
+        .map(|(s, p)| p - beta * (s - forward))
+        .collect();
+    mean_std_err(&adjusted)
+}
+
+/// Prices a european call by Monte Carlo with the sampling drift shifted toward the strike
+///
+/// Draws are shifted by `mu` standard deviations so the median path finishes at the strike, and each payoff is
+/// reweighted by the likelihood ratio `e^(-mu * z + mu^2 / 2)`. Most useful for far out of the money strikes where
+/// plain simulation rarely finishes in the money.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `paths` - number of simulated paths
+/// * `seed` - seed of the random number generator
+#[allow(clippy::too_many_arguments)]
+pub fn mc_euro_call_importance(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    paths: usize,
+    seed: u64,
+) -> McResult {
+    let mut rng = SplitMix64(seed);
+    let discount = E.powf(-r * t);
+    let mu = ((x / s0).ln() - (r - q - sigma.powf(2.0) / 2.0) * t) / (sigma * t.sqrt());
+    let payoffs: Vec<f64> = (0..paths)
+        .map(|_| {
+            let z = sample_standard_normal(&mut rng) + mu;
+            let weight = E.powf(-mu * z + mu.powf(2.0) / 2.0);
+            let s_t = terminal_spot(s0, t, r, q, sigma, z);
+            discount * (s_t - x).max(0.0) * weight
+        })
+        .collect();
+    mean_std_err(&payoffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use mc::*;
+    use price::euro_call;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const PATHS: usize = 50_000;
+    const SEED: u64 = 42;
+
+    #[test]
+    fn test_mc_euro_call() {
+        let p = SAMPLE_PARAMS;
+        let mc = mc_euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma, PATHS, SEED);
+        let analytic = euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((mc.price - analytic).abs() < 4.0 * mc.std_err);
+    }
+
+    #[test]
+    fn test_mc_euro_call_control_variate() {
+        let p = SAMPLE_PARAMS;
+        let plain = mc_euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma, PATHS, SEED);
+        let controlled =
+            mc_euro_call_control_variate(p.s0, p.x, p.t, p.r, p.q, p.sigma, PATHS, SEED);
+        let analytic = euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((controlled.price - analytic).abs() < 4.0 * controlled.std_err);
+        // an at the money call is strongly correlated with the terminal spot
+        assert!(controlled.std_err < 0.6 * plain.std_err);
+    }
+
+    #[test]
+    fn test_mc_euro_call_importance() {
+        let p = SAMPLE_PARAMS;
+        let (strike, t) = (2.0 * p.s0, 0.5);
+        let plain = mc_euro_call(p.s0, strike, t, p.r, p.q, p.sigma, PATHS, SEED);
+        let importance = mc_euro_call_importance(p.s0, strike, t, p.r, p.q, p.sigma, PATHS, SEED);
+        let analytic = euro_call(p.s0, strike, t, p.r, p.q, p.sigma);
+        assert!((importance.price - analytic).abs() < 4.0 * importance.std_err);
+        // variance falls by well over an order of magnitude
+        assert!(importance.std_err.powf(2.0) < 0.1 * plain.std_err.powf(2.0));
+    }
+}
@@ -0,0 +1,5 @@
+mod term_structure;
+mod vol_surface;
+
+pub use self::term_structure::*;
+pub use self::vol_surface::*;
@@ -0,0 +1,94 @@
+// Module containing helpers for implied volatility term structures
+
+/// Interpolates an implied volatility at an arbitrary expiry from a term structure
+///
+/// Interpolation is linear in total variance `sigma^2 * t` rather than in volatility, so increasing
+/// total variance between the quoted expiries (no calendar arbitrage) is preserved. Expiries outside
+/// the quoted range take the volatility of the nearest quoted expiry.
+///
+/// # Arguments
+/// * `expiries` - quoted times to expiration as a percentage of the year, in increasing order
+/// * `vols` - implied volatility quoted at each expiry
+/// * `t` - time to expiration to interpolate at
+pub fn interp_vol_term(expiries: &[f64], vols: &[f64], t: f64) -> f64 {
+    assert_eq!(expiries.len(), vols.len());
+    assert!(!expiries.is_empty());
+
+    let last = expiries.len() - 1;
+    if t <= expiries[0] {
+        return vols[0];
+    }
+    if t >= expiries[last] {
+        return vols[last];
+    }
+
+    let i = expiries.iter().position(|&e| e >= t).unwrap();
+    let (t1, t2) = (expiries[i - 1], expiries[i]);
+    let w1 = vols[i - 1].powf(2.0) * t1;
+    let w2 = vols[i].powf(2.0) * t2;
+    let w = w1 + (w2 - w1) * (t - t1) / (t2 - t1);
+    (w / t).sqrt()
+}
+
+/// Calculates the forward volatility between two expiries implied by their volatilities
+///
+/// Total variance is additive in time, so the variance between the expiries is `sigma2^2 * t2 - sigma1^2 * t1`
+/// spread over `t2 - t1`. Returns `NaN` when the far total variance is below the near one, a calendar arbitrage.
+///
+/// # Arguments
+/// * `sigma1` - implied volatility of the near expiry
+/// * `t1` - time to the near expiry as a percentage of the year
+/// * `sigma2` - implied volatility of the far expiry
+/// * `t2` - time to the far expiry as a percentage of the year
+pub fn forward_vol(sigma1: f64, t1: f64, sigma2: f64, t2: f64) -> f64 {
+    ((sigma2.powf(2.0) * t2 - sigma1.powf(2.0) * t1) / (t2 - t1)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use models::*;
+
+    const EXPIRIES: [f64; 4] = [7.0 / 365.0, 30.0 / 365.0, 90.0 / 365.0, 1.0];
+    // inverted term structure, as after a vol spike
+    const VOLS: [f64; 4] = [1.1, 0.8, 0.7, 0.6];
+
+    #[test]
+    fn test_interp_vol_term_at_pillars() {
+        for (t, vol) in EXPIRIES.iter().zip(VOLS.iter()) {
+            let interp = interp_vol_term(&EXPIRIES, &VOLS, *t);
+            assert!((interp - vol).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_interp_vol_term_no_calendar_arbitrage() {
+        let mut last_variance = 0.0;
+        let steps = 1000;
+        for i in 1..=steps {
+            let t = EXPIRIES[0] + (EXPIRIES[3] - EXPIRIES[0]) * i as f64 / steps as f64;
+            let vol = interp_vol_term(&EXPIRIES, &VOLS, t);
+            let variance = vol * vol * t;
+            assert!(variance >= last_variance);
+            last_variance = variance;
+        }
+    }
+
+    #[test]
+    fn test_forward_vol() {
+        // the forward vol recombines with the near vol into the far total variance
+        let forward = forward_vol(VOLS[1], EXPIRIES[1], VOLS[2], EXPIRIES[2]);
+        let total =
+            VOLS[1].powf(2.0) * EXPIRIES[1] + forward.powf(2.0) * (EXPIRIES[2] - EXPIRIES[1]);
+        assert!((total - VOLS[2].powf(2.0) * EXPIRIES[2]).abs() < 1e-12);
+        // a flat term structure has the same forward vol
+        assert!((forward_vol(0.5, 0.1, 0.5, 0.4) - 0.5).abs() < 1e-12);
+        // falling total variance is an arbitrage
+        assert!(forward_vol(1.0, 0.5, 0.5, 1.0).is_nan());
+    }
+
+    #[test]
+    fn test_interp_vol_term_flat_extrapolation() {
+        assert_eq!(interp_vol_term(&EXPIRIES, &VOLS, 1.0 / 365.0), VOLS[0]);
+        assert_eq!(interp_vol_term(&EXPIRIES, &VOLS, 2.0), VOLS[3]);
+    }
+}
@@ -0,0 +1,220 @@
+// Module containing an implied volatility surface quoted on an expiry by strike grid
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+
+use models::interp_vol_term;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Implied volatility surface quoted on a rectangular grid of expiries and strikes
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "VolGrid"))]
+pub struct VolSurface {
+    // quoted times to expiration as a percentage of the year, in increasing order
+    expiries: Vec<f64>,
+    // quoted strikes, in increasing order
+    strikes: Vec<f64>,
+    // implied volatility of each expiry (rows) at each strike (columns)
+    vols: Vec<Vec<f64>>,
+}
+
+// Unchecked surface as deserialized, validated into a `VolSurface` by `try_from_grid`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct VolGrid {
+    expiries: Vec<f64>,
+    strikes: Vec<f64>,
+    vols: Vec<Vec<f64>>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<VolGrid> for VolSurface {
+    type Error = String;
+
+    fn try_from(grid: VolGrid) -> Result<VolSurface, String> {
+        VolSurface::try_from_grid(grid.expiries, grid.strikes, grid.vols)
+    }
+}
+
+impl VolSurface {
+    /// Builds a surface from a grid of volatilities, one row per expiry and one column per strike
+    ///
+    /// This is the layout of a 2-d array indexed `[expiry, strike]`, as exchanged with numpy.
+    ///
+    /// # Arguments
+    /// * `expiries` - quoted times to expiration as a percentage of the year, in increasing order
+    /// * `strikes` - quoted strikes, in increasing order
+    /// * `vols` - implied volatility of each expiry at each strike
+    ///
+    /// # Panics
+    /// If the grid is rejected by `try_from_grid`
+    pub fn from_grid(expiries: Vec<f64>, strikes: Vec<f64>, vols: Vec<Vec<f64>>) -> VolSurface {
+        match VolSurface::try_from_grid(expiries, strikes, vols) {
+            Ok(surface) => surface,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Builds a surface from a grid of volatilities, returning an error if the grid is not a valid surface
+    ///
+    /// There must be at least one expiry and one strike, both in strictly increasing order, and exactly one row of
+    /// vols per expiry with one vol per strike. This is also the check applied when deserializing.
+    ///
+    /// # Arguments
+    /// * `expiries` - quoted times to expiration as a percentage of the year, in increasing order
+    /// * `strikes` - quoted strikes, in increasing order
+    /// * `vols` - implied volatility of each expiry at each strike
+    pub fn try_from_grid(
+        expiries: Vec<f64>,
+        strikes: Vec<f64>,
+        vols: Vec<Vec<f64>>,
+    ) -> Result<VolSurface, String> {
+        if expiries.is_empty() || strikes.is_empty() {
+            return Err("a vol surface needs at least one expiry and one strike".to_string());
+        }
+        if expiries.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("expiries must be strictly increasing".to_string());
+        }
+        if strikes.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("strikes must be strictly increasing".to_string());
+        }
+        if vols.len() != expiries.len() {
+            return Err(format!(
+                "{} rows of vols for {} expiries",
+                vols.len(),
+                expiries.len()
+            ));
+        }
+        if let Some(i) = vols.iter().position(|row| row.len() != strikes.len()) {
+            return Err(format!(
+                "row {} has {} vols for {} strikes",
+                i,
+                vols[i].len(),
+                strikes.len()
+            ));
+        }
+        Ok(VolSurface {
+            expiries,
+            strikes,
+            vols,
+        })
+    }
+
+    /// Returns the grid of the surface as (expiries, strikes, vols), the inverse of `from_grid`
+    ///
+    /// The vols are one row per expiry and one column per strike.
+    pub fn to_grid(&self) -> (Vec<f64>, Vec<f64>, Vec<Vec<f64>>) {
+        (
+            self.expiries.clone(),
+            self.strikes.clone(),
+            self.vols.clone(),
+        )
+    }
+
+    /// Interpolates the implied volatility at an arbitrary expiry and strike
+    ///
+    /// Each quoted expiry is interpolated linearly in strike, flat beyond the quoted strikes, and the resulting
+    /// term structure in total variance with `interp_vol_term`, flat in volatility beyond the quoted expiries.
+    ///
+    /// # Arguments
+    /// * `t` - time to expiration as a percentage of the year
+    /// * `x` - The strike price of the option
+    pub fn vol(&self, t: f64, x: f64) -> f64 {
+        let term: Vec<f64> = self.vols.iter().map(|row| self.smile(row, x)).collect();
+        interp_vol_term(&self.expiries, &term, t)
+    }
+
+    // Volatility of one quoted expiry at a strike
+    fn smile(&self, row: &[f64], x: f64) -> f64 {
+        let last = self.strikes.len() - 1;
+        if x <= self.strikes[0] {
+            return row[0];
+        }
+        if x >= self.strikes[last] {
+            return row[last];
+        }
+        let i = self.strikes.iter().position(|&k| k >= x).unwrap();
+        let (x1, x2) = (self.strikes[i - 1], self.strikes[i]);
+        row[i - 1] + (row[i] - row[i - 1]) * (x - x1) / (x2 - x1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use models::*;
+
+    fn surface() -> VolSurface {
+        VolSurface::from_grid(
+            vec![7.0 / 365.0, 30.0 / 365.0, 90.0 / 365.0],
+            vec![3000.0, 4000.0, 5000.0],
+            vec![
+                vec![1.2, 1.0, 1.1],
+                vec![0.9, 0.8, 0.85],
+                vec![0.75, 0.7, 0.72],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_vol_surface_grid_round_trip() {
+        let surface = surface();
+        let (expiries, strikes, vols) = surface.to_grid();
+        assert_eq!(expiries, surface.expiries);
+        assert_eq!(strikes, surface.strikes);
+        assert_eq!(vols, surface.vols);
+        assert_eq!(VolSurface::from_grid(expiries, strikes, vols), surface);
+    }
+
+    #[test]
+    fn test_vol_surface_interpolation() {
+        let surface = surface();
+        assert!((surface.vol(30.0 / 365.0, 4000.0) - 0.8).abs() < 1e-12);
+        // linear in strike within an expiry and flat beyond the quoted strikes
+        assert!((surface.vol(30.0 / 365.0, 3500.0) - 0.85).abs() < 1e-12);
+        assert!((surface.vol(30.0 / 365.0, 2000.0) - 0.9).abs() < 1e-12);
+        // between expiries it matches the term structure of the quoted strike
+        let term = [1.0, 0.8, 0.7];
+        let expected = interp_vol_term(&surface.expiries, &term, 60.0 / 365.0);
+        assert!((surface.vol(60.0 / 365.0, 4000.0) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "1 rows of vols for 2 expiries")]
+    fn test_vol_surface_from_grid_shape() {
+        VolSurface::from_grid(vec![0.1, 0.2], vec![1.0, 2.0], vec![vec![0.5, 0.5]]);
+    }
+
+    #[test]
+    fn test_vol_surface_try_from_grid() {
+        let ragged = VolSurface::try_from_grid(
+            vec![0.1, 0.2],
+            vec![1.0, 2.0],
+            vec![vec![0.5, 0.5], vec![0.5]],
+        );
+        assert_eq!(ragged, Err("row 1 has 1 vols for 2 strikes".to_string()));
+        let unsorted =
+            VolSurface::try_from_grid(vec![0.2, 0.1], vec![1.0], vec![vec![0.5], vec![0.5]]);
+        assert_eq!(
+            unsorted,
+            Err("expiries must be strictly increasing".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_vol_surface_serde_round_trip() {
+        let surface = surface();
+        let json = serde_json::to_string(&surface).unwrap();
+        let parsed: VolSurface = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, surface);
+
+        // a ragged grid is rejected rather than panicking in `vol` later
+        let ragged = r#"{"expiries":[0.1,0.2],"strikes":[1.0,2.0],"vols":[[0.5,0.5],[0.5]]}"#;
+        let err = serde_json::from_str::<VolSurface>(ragged).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("row 1 has 1 vols for 2 strikes"));
+    }
+}
@@ -0,0 +1,337 @@
+// Module for aggregating greeks across the positions of a book
+use common::OptionType;
+use greeks::{delta, vomma, Greeks};
+use price::price;
+
+/// Sums the greeks of a set of positions, each weighted by its signed quantity
+///
+/// # Arguments
+/// * `positions` - pairs of quantity (negative when short) and the greeks of one unit of the position
+pub fn portfolio_greeks(positions: &[(f64, Greeks)]) -> Greeks {
+    positions
+        .iter()
+        .fold(Greeks::default(), |total, &(quantity, greeks)| {
+            total + greeks * quantity
+        })
+}
+
+/// A holding of European options on a single underlying
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub kind: OptionType,
+    /// The strike price of the option
+    pub x: f64,
+    /// signed quantity, negative when short
+    pub quantity: f64,
+}
+
+/// Sums the vomma of a set of option positions sharing one underlying, expiry and volatility
+///
+/// Vomma is the same for calls and puts at a strike. A positive total means vega grows as volatility rises, which
+/// a short volatility book pays for when volatility spikes. Like `vomma` it is per volatility point.
+///
+/// # Arguments
+/// * `positions` - The option positions
+/// * `s0` - The underlying price of the options
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn portfolio_vomma(positions: &[Position], s0: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    positions
+        .iter()
+        .map(|position| position.quantity * vomma(s0, position.x, t, r, q, sigma))
+        .sum()
+}
+
+/// Calculates the lambda (omega) of a multi-leg option spread on one underlying
+///
+/// The spread's elasticity is `net_delta * s0 / net_premium`, the percentage change in its value for a percentage
+/// change in the underlying price, which is the premium weighted average of the lambdas of its legs. A spread with
+/// no net premium has no defined lambda and returns an infinite or `NaN` value.
+///
+/// # Arguments
+/// * `legs` - The option legs of the spread
+/// * `s0` - The underlying price of the options
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn spread_lambda(legs: &[Position], s0: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let (net_delta, net_premium) = legs
+        .iter()
+        .fold((0.0, 0.0), |(delta_sum, premium_sum), leg| {
+            (
+                delta_sum + leg.quantity * delta(leg.kind, s0, leg.x, t, r, q, sigma),
+                premium_sum + leg.quantity * price(leg.kind, s0, leg.x, t, r, q, sigma),
+            )
+        });
+    net_delta * s0 / net_premium
+}
+
+/// A book of positions spread across several underlyings
+///
+/// Each position is tagged with the index of its underlying, which also indexes the spots, volatilities and
+/// correlation matrix passed to the risk measures. Greeks are only aggregated within an underlying, since a
+/// delta in BTC and a delta in ETH are not the same risk.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MultiAssetPortfolio {
+    /// (underlying index, signed quantity, greeks of one unit) of each position
+    pub positions: Vec<(usize, f64, Greeks)>,
+}
+
+impl MultiAssetPortfolio {
+    /// Creates an empty book
+    pub fn new() -> MultiAssetPortfolio {
+        MultiAssetPortfolio::default()
+    }
+
+    /// Adds a position on an underlying
+    ///
+    /// # Arguments
+    /// * `underlying` - index of the underlying
+    /// * `quantity` - signed quantity, negative when short
+    /// * `greeks` - greeks of one unit of the position
+    pub fn add(&mut self, underlying: usize, quantity: f64, greeks: Greeks) {
+        self.positions.push((underlying, quantity, greeks));
+    }
+
+    /// Quantity weighted greeks of each underlying, indexed by underlying
+    pub fn greeks_by_underlying(&self) -> Vec<Greeks> {
+        let count = self
+            .positions
+            .iter()
+            .map(|&(id, _, _)| id + 1)
+            .max()
+            .unwrap_or(0);
+        let mut totals = vec![Greeks::default(); count];
+        for &(id, quantity, greeks) in &self.positions {
+            totals[id] = totals[id] + greeks * quantity;
+        }
+        totals
+    }
+
+    /// Calculates the delta value at risk of the book with correlated underlyings
+    ///
+    /// The dollar delta of each underlying moves with standard deviation `delta * s0 * sigma * sqrt(horizon)`, and
+    /// the book's VaR is `z * sqrt(e' C e)` for those exposures `e` and correlation matrix `C`. Uncorrelated
+    /// underlyings combine as the root sum of squares of their own VaRs, perfectly correlated ones add up.
+    ///
+    /// # Arguments
+    /// * `spots` - price of each underlying
+    /// * `vols` - volatility of each underlying
+    /// * `correlation` - correlation matrix of the underlyings' returns
+    /// * `horizon` - holding period as a percentage of the year
+    /// * `z` - number of standard deviations, e.g. 1.645 for 95%
+    pub fn delta_var(
+        &self,
+        spots: &[f64],
+        vols: &[f64],
+        correlation: &[Vec<f64>],
+        horizon: f64,
+        z: f64,
+    ) -> f64 {
+        let exposures: Vec<f64> = self
+            .greeks_by_underlying()
+            .iter()
+            .enumerate()
+            .map(|(id, greeks)| greeks.delta * spots[id] * vols[id] * horizon.sqrt())
+            .collect();
+        correlated_var(&exposures, correlation, z)
+    }
+
+    /// Calculates the vega value at risk of the book with correlated implied volatility moves
+    ///
+    /// Like `delta_var` with the exposure of each underlying its per point vega times the standard deviation of its
+    /// implied volatility move over the horizon, in volatility points.
+    ///
+    /// # Arguments
+    /// * `vol_moves` - standard deviation of each underlying's implied volatility move in points
+    /// * `correlation` - correlation matrix of the implied volatility moves
+    /// * `z` - number of standard deviations, e.g. 1.645 for 95%
+    pub fn vega_var(&self, vol_moves: &[f64], correlation: &[Vec<f64>], z: f64) -> f64 {
+        let exposures: Vec<f64> = self
+            .greeks_by_underlying()
+            .iter()
+            .enumerate()
+            .map(|(id, greeks)| greeks.vega * vol_moves[id])
+            .collect();
+        correlated_var(&exposures, correlation, z)
+    }
+}
+
+// 'z' standard deviations of a sum of exposures with one standard deviation moves 'exposures' and the given
+// correlation matrix
+fn correlated_var(exposures: &[f64], correlation: &[Vec<f64>], z: f64) -> f64 {
+    let variance: f64 = exposures
+        .iter()
+        .enumerate()
+        .map(|(i, e_i)| {
+            exposures
+                .iter()
+                .enumerate()
+                .map(|(j, e_j)| e_i * correlation[i][j] * e_j)
+                .sum::<f64>()
+        })
+        .sum();
+    z * variance.max(0.0).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use greeks::*;
+    use portfolio::*;
+    use price::euro_call;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_portfolio_greeks_delta_hedged() {
+        let p = SAMPLE_PARAMS;
+        let call = gbsm_greeks(
+            OptionType::Call,
+            p.s0,
+            p.x,
+            p.t,
+            p.r,
+            p.r - p.q,
+            p.sigma,
+            p.days_per_year,
+        );
+        let forward = forward_greeks(p.s0, p.s0, p.t, p.r, p.q, p.days_per_year);
+        let hedge = -call.delta / forward.delta;
+        let book = portfolio_greeks(&[(10.0, call), (10.0 * hedge, forward)]);
+
+        assert!(book.delta.abs() < 1e-12);
+        assert!((book.gamma - 10.0 * call.gamma).abs() < 1e-12);
+        assert!((book.vega - 10.0 * call.vega).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_multi_asset_portfolio() {
+        let p = SAMPLE_PARAMS;
+        let call = |s0: f64| {
+            gbsm_greeks(
+                OptionType::Call,
+                s0,
+                s0,
+                p.t,
+                p.r,
+                p.r - p.q,
+                p.sigma,
+                p.days_per_year,
+            )
+        };
+        let (btc, eth) = (0, 1);
+        let spots = [60000.0, 3000.0];
+        let vols = [0.6, 0.8];
+        let mut book = MultiAssetPortfolio::new();
+        book.add(btc, 2.0, call(spots[btc]));
+        book.add(eth, 30.0, call(spots[eth]));
+        book.add(eth, -10.0, call(spots[eth]));
+
+        let by_underlying = book.greeks_by_underlying();
+        assert_eq!(by_underlying.len(), 2);
+        assert!((by_underlying[btc].delta - 2.0 * call(spots[btc]).delta).abs() < 1e-12);
+        assert!((by_underlying[eth].delta - 20.0 * call(spots[eth]).delta).abs() < 1e-12);
+
+        let (horizon, z) = (1.0 / p.days_per_year, 1.645);
+        let single = |id: usize| {
+            let mut alone = MultiAssetPortfolio::new();
+            for &(underlying, quantity, greeks) in &book.positions {
+                if underlying == id {
+                    alone.add(0, quantity, greeks);
+                }
+            }
+            alone.delta_var(&spots[id..=id], &vols[id..=id], &[vec![1.0]], horizon, z)
+        };
+
+        // uncorrelated underlyings combine as a root sum of squares
+        let uncorrelated = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let var = book.delta_var(&spots, &vols, &uncorrelated, horizon, z);
+        let rss = (single(btc).powf(2.0) + single(eth).powf(2.0)).sqrt();
+        assert!((var - rss).abs() < 1e-9 * rss);
+
+        // perfectly correlated ones add up
+        let correlated = vec![vec![1.0, 1.0], vec![1.0, 1.0]];
+        let var = book.delta_var(&spots, &vols, &correlated, horizon, z);
+        assert!((var - (single(btc) + single(eth))).abs() < 1e-9 * var);
+
+        // vega risk follows the same rule
+        let vol_moves = [2.0, 3.0];
+        let var = book.vega_var(&vol_moves, &uncorrelated, z);
+        let btc_vega = z * by_underlying[btc].vega * vol_moves[btc];
+        let eth_vega = z * by_underlying[eth].vega * vol_moves[eth];
+        assert!((var - (btc_vega.powf(2.0) + eth_vega.powf(2.0)).sqrt()).abs() < 1e-9 * var);
+    }
+
+    #[test]
+    fn test_portfolio_vomma() {
+        let p = SAMPLE_PARAMS;
+        let straddle = |x: f64, quantity: f64| {
+            vec![
+                Position {
+                    kind: OptionType::Call,
+                    x,
+                    quantity,
+                },
+                Position {
+                    kind: OptionType::Put,
+                    x,
+                    quantity,
+                },
+            ]
+        };
+        let vomma_of =
+            |positions: &[Position]| portfolio_vomma(positions, p.s0, p.t, p.r, p.q, p.sigma);
+
+        // a long straddle struck away from spot gains vega as volatility rises
+        let long = vomma_of(&straddle(75.0, 10.0));
+        let single = vomma(p.s0, 75.0, p.t, p.r, p.q, p.sigma);
+        assert!(long > 0.0);
+        assert!((long - 20.0 * single).abs() < 1e-12);
+        assert_eq!(vomma_of(&straddle(75.0, -10.0)), -long);
+
+        // at the money vomma all but vanishes, the vega of a straddle there barely depends on volatility
+        assert!(vomma_of(&straddle(p.x, 10.0)).abs() < 0.1 * long);
+    }
+
+    #[test]
+    fn test_spread_lambda() {
+        let p = SAMPLE_PARAMS;
+        let lambda_of =
+            |legs: &[Position], s0: f64| spread_lambda(legs, s0, p.t, p.r, p.q, p.sigma);
+        let call = |x: f64, quantity: f64| Position {
+            kind: OptionType::Call,
+            x,
+            quantity,
+        };
+
+        // a single leg is the lambda of the option
+        let premium = euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let single = lambda_call(p.s0, p.x, p.t, p.r, p.q, p.sigma, premium);
+        assert!((lambda_of(&[call(p.x, 3.0)], p.s0) - single).abs() < 1e-12);
+
+        // a bull call spread matches the elasticity of its value by finite difference
+        let bull = [call(60.0, 1.0), call(70.0, -1.0)];
+        let value = |s0: f64| {
+            euro_call(s0, 60.0, p.t, p.r, p.q, p.sigma)
+                - euro_call(s0, 70.0, p.t, p.r, p.q, p.sigma)
+        };
+        let h = 1e-3;
+        let elasticity = (value(p.s0 + h) - value(p.s0 - h)) / (2.0 * h) * p.s0 / value(p.s0);
+        let lambda = lambda_of(&bull, p.s0);
+        assert!(lambda > 0.0);
+        assert!((lambda - elasticity).abs() < 1e-6 * lambda);
+
+        // a bear put spread is the mirror image, losing value as the underlying rises
+        let put = |x: f64, quantity: f64| Position {
+            kind: OptionType::Put,
+            x,
+            quantity,
+        };
+        let bear = [put(70.0, 1.0), put(60.0, -1.0)];
+        assert!(lambda_of(&bear, p.s0) < 0.0);
+    }
+}
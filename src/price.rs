@@ -1,9 +1,20 @@
+mod asian;
+mod barrier;
+mod compound;
+mod lookback;
+
+pub use self::asian::*;
+pub use self::barrier::*;
+pub use self::compound::*;
+pub use self::lookback::*;
+
 use std::f64::consts::E;
 
 use common::*;
-use stats::cnd;
+use stats::{cnd, pdf};
+use validation::{input_warnings, validate_inputs};
 
-/// Evaluates the price of a European call option on an underlying which does not pay dividends before expiry of the option using the Black-Scholes model
+/// Evaluates the price of a European call option on an underlying paying a continuous dividend yield using the Black-Scholes model
 ///
 /// # Arguments
 /// * `s0` - The underlying price of the option
@@ -15,12 +26,13 @@ use stats::cnd;
 pub fn euro_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
     let d1 = d1(s0, x, t, r, q, sigma);
     let d2 = d2_d1(t, sigma, d1);
-    let arg1 = s0 * cnd(d1);
+    let arg1 = s0 * E.powf(-q * t) * cnd(d1);
     let arg2 = x * E.powf(-r * t) * cnd(d2);
-    return arg1 - arg2;
+    // deep out of the money both terms vanish and rounding can leave a tiny negative
+    (arg1 - arg2).max(0.0)
 }
 
-/// Evaluate the price of a European put option on an underlying which does not pay dividents before expiry of the option using the Black-Scholes model
+/// Evaluate the price of a European put option on an underlying paying a continuous dividend yield using the Black-Scholes model
 ///
 /// # Arguments
 /// * `s0` - The underlying price of the option
@@ -32,52 +44,769 @@ pub fn euro_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
 pub fn euro_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
     let d1 = d1(s0, x, t, r, q, sigma);
     let d2 = d2_d1(t, sigma, d1);
-    let arg1 = s0 * cnd(-d1);
+    let arg1 = s0 * E.powf(-q * t) * cnd(-d1);
     let arg2 = x * E.powf(-r * t) * cnd(-d2);
-    return -arg1 + arg2;
+    (-arg1 + arg2).max(0.0)
+}
+
+/// Evaluates the price of a European call or put option, dispatching to `euro_call` or `euro_put`
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn price(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    match kind {
+        OptionType::Call => euro_call(s0, x, t, r, q, sigma),
+        OptionType::Put => euro_put(s0, x, t, r, q, sigma),
+    }
+}
+
+/// Evaluates the price of a European call option, rejecting inputs outside the model's domain
+///
+/// Returns an error for non-positive or non-finite inputs. Inputs which merely look like they were given in
+/// the wrong units are priced, use `euro_call_strict` to reject those as well.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn euro_call_checked(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> Result<f64, String> {
+    validate_inputs(s0, x, t, r, q, sigma)?;
+    Ok(euro_call(s0, x, t, r, q, sigma))
+}
+
+/// Evaluates the price of a European put option, rejecting inputs outside the model's domain
+///
+/// Returns an error for non-positive or non-finite inputs. Inputs which merely look like they were given in
+/// the wrong units are priced, use `euro_put_strict` to reject those as well.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn euro_put_checked(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> Result<f64, String> {
+    validate_inputs(s0, x, t, r, q, sigma)?;
+    Ok(euro_put(s0, x, t, r, q, sigma))
+}
+
+/// Evaluates the price of a European call option, also rejecting inputs which look like they were given in the wrong units
+///
+/// As `euro_call_checked`, but a volatility of `50` meaning 50% or a time to expiry in days is an error rather than
+/// a price. See `input_warnings` for the ranges flagged.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn euro_call_strict(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> Result<f64, String> {
+    check_units(s0, x, t, r, q, sigma)?;
+    euro_call_checked(s0, x, t, r, q, sigma)
+}
+
+/// Evaluates the price of a European put option, also rejecting inputs which look like they were given in the wrong units
+///
+/// As `euro_put_checked`, but a volatility of `50` meaning 50% or a time to expiry in days is an error rather than
+/// a price. See `input_warnings` for the ranges flagged.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn euro_put_strict(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> Result<f64, String> {
+    check_units(s0, x, t, r, q, sigma)?;
+    euro_put_checked(s0, x, t, r, q, sigma)
+}
+
+fn check_units(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> Result<(), String> {
+    let warnings = input_warnings(s0, x, t, r, q, sigma);
+    if warnings.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("suspicious option inputs: {}", warnings.join("; ")))
+    }
+}
+
+/// Evaluates the price of a European call option on an underlying which can jump to zero (default or depeg)
+///
+/// The underlying defaults with constant intensity `hazard`. To remain a martingale it drifts at
+/// `r - q + hazard` while it survives, and the call is worthless once it has defaulted, so the price
+/// is the Black-Scholes price discounted at `r + hazard`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `hazard` - default intensity of the underlying, per year
+pub fn euro_call_with_default(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    hazard: f64,
+) -> f64 {
+    euro_call(s0, x, t, r + hazard, q, sigma)
+}
+
+/// Evaluates the price of a European put option on an underlying which can jump to zero (default or depeg)
+///
+/// The surviving part is priced as in `euro_call_with_default`, and with probability `1 - e^(-hazard * t)`
+/// the underlying defaults and the put pays the full strike. This models depeg-protection puts.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `hazard` - default intensity of the underlying, per year
+pub fn euro_put_with_default(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    hazard: f64,
+) -> f64 {
+    let survival = euro_put(s0, x, t, r + hazard, q, sigma);
+    let default = x * E.powf(-r * t) * (1.0 - E.powf(-hazard * t));
+    survival + default
+}
+
+/// Evaluates the price of a European option under the generalized Black-Scholes-Merton model
+///
+/// The cost of carry `b` selects the model:
+/// * `b = r` - Black-Scholes on a non-dividend paying underlying
+/// * `b = r - q` - Black-Scholes-Merton with a continuous dividend yield `q`
+/// * `b = 0` - Black-76 on a futures price
+/// * `b = r - rf` - Garman-Kohlhagen on an FX rate with foreign interest rate `rf`
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying (or futures) price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `b` - continuously compounded cost of carry
+/// * `sigma` - volatility
+pub fn gbsm_price(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, b: f64, sigma: f64) -> f64 {
+    price(kind, s0, x, t, r, r - b, sigma)
+}
+
+/// Evaluates the price of a European option on a forward under the Bachelier (normal) model
+///
+/// The forward follows an arithmetic Brownian motion, so it can go negative, as rates and spreads do.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `forward` - The forward price (or rate) of the underlying
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `sigma` - normal volatility, in price units per square root of a year
+pub fn bachelier_price(kind: OptionType, forward: f64, x: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    let sd = sigma * t.sqrt();
+    let d = (forward - x) / sd;
+    let intrinsic = match kind {
+        OptionType::Call => (forward - x) * cnd(d),
+        OptionType::Put => (x - forward) * cnd(-d),
+    };
+    E.powf(-r * t) * (intrinsic + sd * pdf(d))
+}
+
+/// Asset class of an instrument, which fixes the pricing model and its carry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssetClass {
+    /// Stock or index paying a continuous dividend yield `q`, priced with Black-Scholes-Merton
+    Equity { q: f64 },
+    /// Currency pair quoted as the domestic price of the foreign currency, priced with Garman-Kohlhagen
+    Fx { rf: f64 },
+    /// Futures price, priced with Black-76
+    Future,
+    /// Forward rate, which may be negative, priced with Bachelier on a normal volatility
+    Rate,
+}
+
+/// A European option on any asset class, the input of `price_auto`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InstrumentSpec {
+    pub asset_class: AssetClass,
+    pub kind: OptionType,
+    /// The underlying price (or futures price, or forward rate) of the option
+    pub s0: f64,
+    /// The strike price of the option
+    pub x: f64,
+    /// time to expiration as a percentage of the year
+    pub t: f64,
+    /// continuously compounded risk-free interest rate
+    pub r: f64,
+    /// volatility, lognormal except for `AssetClass::Rate` where it is normal
+    pub sigma: f64,
+}
+
+/// Evaluates the price of a European option with the model of its asset class
+///
+/// Equities, FX and futures go through `gbsm_price` with a cost of carry of `r - q`, `r - rf` and zero
+/// respectively, and rates through `bachelier_price`.
+///
+/// # Arguments
+/// * `spec` - The option and the asset class of its underlying
+pub fn price_auto(spec: InstrumentSpec) -> f64 {
+    let InstrumentSpec {
+        asset_class,
+        kind,
+        s0,
+        x,
+        t,
+        r,
+        sigma,
+    } = spec;
+    match asset_class {
+        AssetClass::Equity { q } => gbsm_price(kind, s0, x, t, r, r - q, sigma),
+        AssetClass::Fx { rf } => gbsm_price(kind, s0, x, t, r, r - rf, sigma),
+        AssetClass::Future => gbsm_price(kind, s0, x, t, r, 0.0, sigma),
+        AssetClass::Rate => bachelier_price(kind, s0, x, t, r, sigma),
+    }
+}
+
+/// Evaluates a call on a forward under Black-76 and under Bachelier side by side, returning `(black, bachelier)`
+///
+/// At the money the two agree when `sigma_n ~ sigma_ln * f`. Away from the money they part, the lognormal
+/// model putting less weight on low forwards and more on high ones, and near a zero forward only Bachelier allows the
+/// underlying to cross it. The difference `black - bachelier` is the model risk of choosing one over the other.
+///
+/// # Arguments
+/// * `f` - The forward price of the underlying
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `sigma_ln` - lognormal volatility used by Black-76
+/// * `sigma_n` - normal volatility used by Bachelier, in price units per square root of a year
+pub fn model_comparison(f: f64, x: f64, t: f64, r: f64, sigma_ln: f64, sigma_n: f64) -> (f64, f64) {
+    let black = gbsm_price(OptionType::Call, f, x, t, r, 0.0, sigma_ln);
+    let bachelier = bachelier_price(OptionType::Call, f, x, t, r, sigma_n);
+    (black, bachelier)
+}
+
+/// Approximates the price of an at-the-money-forward call or put with the Brenner-Subrahmanyam formula
+///
+/// At the money forward the call and put are worth the same, `0.4 * F * e^(-rt) * sigma * sqrt(t)`,
+/// which follows from `N(x) - N(-x) ~ 0.8x` for small `x = sigma * sqrt(t) / 2`. A straddle is twice this.
+///
+/// # Arguments
+/// * `forward` - The forward price of the underlying, also the strike
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `sigma` - volatility
+pub fn atmf_price(forward: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    0.4 * forward * E.powf(-r * t) * sigma * t.sqrt()
+}
+
+/// Evaluates the exact price of an at-the-money-forward call or put using Black-76
+///
+/// # Arguments
+/// * `forward` - The forward price of the underlying, also the strike
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `sigma` - volatility
+pub fn atmf_price_exact(forward: f64, t: f64, r: f64, sigma: f64) -> f64 {
+    let half_sd = sigma * t.sqrt() / 2.0;
+    forward * E.powf(-r * t) * (cnd(half_sd) - cnd(-half_sd))
+}
+
+/// Inverts the Brenner-Subrahmanyam approximation to quote the volatility of an at-the-money-forward option
+///
+/// # Arguments
+/// * `price` - Price of the at-the-money-forward call or put
+/// * `forward` - The forward price of the underlying, also the strike
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+pub fn atmf_vol_from_price(price: f64, forward: f64, t: f64, r: f64) -> f64 {
+    price / (0.4 * forward * E.powf(-r * t) * t.sqrt())
+}
+
+/// Evaluates the price of a European cash-or-nothing binary call which pays one unit of cash if the underlying finishes above the strike
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn binary_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d2 = d2(s0, x, t, r, q, sigma);
+    E.powf(-r * t) * cnd(d2)
+}
+
+/// Evaluates the price of a European cash-or-nothing binary put which pays one unit of cash if the underlying finishes below the strike
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn binary_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d2 = d2(s0, x, t, r, q, sigma);
+    E.powf(-r * t) * cnd(-d2)
+}
+
+/// Evaluates the price of a binary which pays `cash_payout` if it finishes in the money and `rebate` otherwise
+///
+/// The rebate is paid at expiry, so the price is a plain binary on `cash_payout - rebate` plus the discounted rebate.
+///
+/// # Arguments
+/// * `kind` - Whether the binary pays above (call) or below (put) the strike
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `cash_payout` - cash paid if the binary finishes in the money
+/// * `rebate` - cash paid if the binary finishes out of the money
+#[allow(clippy::too_many_arguments)]
+pub fn binary_with_rebate(
+    kind: OptionType,
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    cash_payout: f64,
+    rebate: f64,
+) -> f64 {
+    let binary = match kind {
+        OptionType::Call => binary_call(s0, x, t, r, q, sigma),
+        OptionType::Put => binary_put(s0, x, t, r, q, sigma),
+    };
+    cash_payout * binary + rebate * (E.powf(-r * t) - binary)
+}
+
+/// Evaluates the call spread which replicates a binary call, the way binaries are hedged in practice
+///
+/// The digital payoff is discontinuous at the strike, so its delta and gamma blow up as `t` goes to zero (pin risk).
+/// Desks instead hold a call at `x - spread / 2` and sell a call at `x + spread / 2`, scaled by `1 / spread`,
+/// which over-hedges the binary and converges to it as the spread narrows.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the binary
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+/// * `spread` - distance between the two call strikes
+pub fn binary_overhedge_spread(
+    s0: f64,
+    x: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+    spread: f64,
+) -> f64 {
+    let long = euro_call(s0, x - spread / 2.0, t, r, q, sigma);
+    let short = euro_call(s0, x + spread / 2.0, t, r, q, sigma);
+    (long - short) / spread
+}
+
+/// Calculates the financing rate implied by the price of a box spread
+///
+/// A box, long the call spread and long the put spread between the same two strikes, pays `x_high - x_low` at
+/// expiry whatever happens, so its price `(x_high - x_low) * e^(-rt)` pins down the rate `r`.
+///
+/// # Arguments
+/// * `call_spread_price` - price of the call spread, long the `x_low` call and short the `x_high` call
+/// * `put_spread_price` - price of the put spread, long the `x_high` put and short the `x_low` put
+/// * `x_low` - The lower strike of the box
+/// * `x_high` - The upper strike of the box
+/// * `t` - time to expiration as a percentage of the year
+pub fn implied_rate_from_box(
+    call_spread_price: f64,
+    put_spread_price: f64,
+    x_low: f64,
+    x_high: f64,
+    t: f64,
+) -> f64 {
+    -((call_spread_price + put_spread_price) / (x_high - x_low)).ln() / t
 }
 
 #[cfg(test)]
 mod tests {
 
+    use common::OptionType;
     use price::*;
+    use std::f64::consts::E;
+    use test_fixtures::SAMPLE_PARAMS;
 
-    const UNDERLYING: f64 = 64.68;
-    const STRIKE: f64 = 65.00;
-    const VOL: f64 = 0.5051;
-    const INTEREST_RATE: f64 = 0.0150;
-    const DIV_YIELD: f64 = 0.0210;
-    const DAYS_PER_YEAR: f64 = 365.0;
-    const TIME_TO_EXPIRY: f64 = 23.0 / DAYS_PER_YEAR;
-
-    const E_EURO_CALL_PRICE: f64 = 3.148;
-    const E_EURO_PUT_PRICE: f64 = 3.406;
+    // Black-Scholes-Merton prices with the spot leg discounted by e^(-qt). The pricers used to leave the spot
+    // leg undiscounted (3.148 and 3.406), which broke put-call parity whenever q != 0, see
+    // `test_euro_put_call_parity_with_dividends`
+    const E_EURO_CALL_PRICE: f64 = 3.105;
+    const E_EURO_PUT_PRICE: f64 = 3.449;
+    const E_BINARY_CALL_PRICE: f64 = 0.4576;
 
     #[test]
     fn test_euro_call() {
-        let price = euro_call(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
-        );
+        let p = SAMPLE_PARAMS;
+        let price = euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
         let abs = (price - E_EURO_CALL_PRICE).abs();
         assert!(abs < 0.001);
     }
 
     #[test]
     fn test_euro_put() {
-        let price = euro_put(
-            UNDERLYING,
-            STRIKE,
-            TIME_TO_EXPIRY,
-            INTEREST_RATE,
-            DIV_YIELD,
-            VOL,
+        let p = SAMPLE_PARAMS;
+        let price = euro_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let abs = (price - E_EURO_PUT_PRICE).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_euro_put_call_parity_with_dividends() {
+        let p = SAMPLE_PARAMS;
+        // c - p = s0 e^(-qt) - x e^(-rt) holds for any model, so it pins the dividend discount of the spot leg
+        let call = euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let put = euro_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let forward_value = p.s0 * E.powf(-p.q * p.t) - p.x * E.powf(-p.r * p.t);
+        assert!((call - put - forward_value).abs() < 1e-12);
+        assert!((E_EURO_CALL_PRICE - E_EURO_PUT_PRICE - forward_value).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_binary_with_rebate() {
+        let p = SAMPLE_PARAMS;
+        let plain = 10.0 * binary_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let no_rebate = binary_with_rebate(
+            OptionType::Put,
+            p.s0,
+            p.x,
+            p.t,
+            p.r,
+            p.q,
+            p.sigma,
+            10.0,
+            0.0,
+        );
+        assert!((no_rebate - plain).abs() < 1e-12);
+
+        // paying the same either way is a zero coupon bond
+        let bond = binary_with_rebate(
+            OptionType::Call,
+            p.s0,
+            p.x,
+            p.t,
+            p.r,
+            p.q,
+            p.sigma,
+            10.0,
+            10.0,
+        );
+        let abs = (bond - 10.0 * E.powf(-p.r * p.t)).abs();
+        assert!(abs < 1e-12);
+    }
+
+    #[test]
+    fn test_euro_checked() {
+        let p = SAMPLE_PARAMS;
+        let call = euro_call_checked(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((call.unwrap() - E_EURO_CALL_PRICE).abs() < 0.001);
+        let put = euro_put_checked(p.s0, p.x, p.t, p.r, p.q, 0.0);
+        assert!(put.is_err());
+    }
+
+    #[test]
+    fn test_euro_checked_percent_vol() {
+        let p = SAMPLE_PARAMS;
+        let call = euro_call_checked(p.s0, p.x, p.t, p.r, p.q, 50.0);
+        // a volatility of 5000% is unusual but still priced
+        assert!(call.is_ok());
+        let strict = euro_call_strict(p.s0, p.x, p.t, p.r, p.q, 50.0);
+        assert!(strict.unwrap_err().starts_with("suspicious option inputs"));
+        let put = euro_put_strict(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!((put.unwrap() - E_EURO_PUT_PRICE).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_euro_call_deep_otm() {
+        let p = SAMPLE_PARAMS;
+        for multiple in [5.0, 10.0, 50.0].iter() {
+            for vol in [0.05, p.sigma, 2.0].iter() {
+                let price = euro_call(p.s0, p.x * multiple, p.t, p.r, p.q, *vol);
+                // a call is never worth more than the discounted underlying
+                let bound = p.s0 * (-p.q * p.t).exp();
+                assert!(price >= 0.0);
+                assert!(price < bound);
+            }
+        }
+    }
+
+    #[test]
+    fn test_euro_put_deep_otm() {
+        let p = SAMPLE_PARAMS;
+        for multiple in [0.2, 0.1, 0.02].iter() {
+            for vol in [0.05, p.sigma, 2.0].iter() {
+                let strike = p.x * multiple;
+                let price = euro_put(p.s0, strike, p.t, p.r, p.q, *vol);
+                // a put is never worth more than the discounted strike
+                let bound = strike * (-p.r * p.t).exp();
+                assert!(price >= 0.0);
+                assert!(price < bound);
+            }
+        }
+    }
+
+    #[test]
+    fn test_atmf_price() {
+        let p = SAMPLE_PARAMS;
+        let forward = p.s0 * ((p.r - p.q) * p.t).exp();
+        let approx = atmf_price(forward, p.t, p.r, p.sigma);
+        let exact = atmf_price_exact(forward, p.t, p.r, p.sigma);
+        let call = euro_call(p.s0, forward, p.t, p.r, p.q, p.sigma);
+        assert!((exact - call).abs() < 0.001);
+        // the approximation is good to a fraction of a percent for short dated options
+        assert!((approx - exact).abs() / exact < 0.005);
+
+        let vol = atmf_vol_from_price(approx, forward, p.t, p.r);
+        assert!((vol - p.sigma).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_binary_call() {
+        let p = SAMPLE_PARAMS;
+        let price = binary_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let abs = (price - E_BINARY_CALL_PRICE).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_binary_parity() {
+        let p = SAMPLE_PARAMS;
+        let call = binary_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let put = binary_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let discount = (-p.r * p.t).exp();
+        let abs = (call + put - discount).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_implied_rate_from_box() {
+        let p = SAMPLE_PARAMS;
+        let (low, high) = (60.0, 70.0);
+        for &rate in [p.r, 0.08, -0.005].iter() {
+            let call = |x: f64| euro_call(p.s0, x, p.t, rate, p.q, p.sigma);
+            let put = |x: f64| euro_put(p.s0, x, p.t, rate, p.q, p.sigma);
+            let implied =
+                implied_rate_from_box(call(low) - call(high), put(high) - put(low), low, high, p.t);
+            assert!((implied - rate).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_binary_overhedge_spread_converges() {
+        let p = SAMPLE_PARAMS;
+        let binary = binary_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let mut last_abs = f64::MAX;
+        for spread in [4.0, 1.0, 0.25, 0.01].iter() {
+            let replication = binary_overhedge_spread(p.s0, p.x, p.t, p.r, p.q, p.sigma, *spread);
+            let abs = (replication - binary).abs();
+            assert!(abs <= last_abs);
+            last_abs = abs;
+        }
+        assert!(last_abs < 0.001);
+    }
+
+    #[test]
+    fn test_gbsm_black_scholes() {
+        let p = SAMPLE_PARAMS;
+        let price = gbsm_price(OptionType::Call, p.s0, p.x, p.t, p.r, p.r, p.sigma);
+        let e_price = euro_call(p.s0, p.x, p.t, p.r, 0.0, p.sigma);
+        let abs = (price - e_price).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_gbsm_dividend_yield() {
+        let p = SAMPLE_PARAMS;
+        let call = gbsm_price(OptionType::Call, p.s0, p.x, p.t, p.r, p.r - p.q, p.sigma);
+        let put = gbsm_price(OptionType::Put, p.s0, p.x, p.t, p.r, p.r - p.q, p.sigma);
+        assert!((call - E_EURO_CALL_PRICE).abs() < 0.001);
+        assert!((put - E_EURO_PUT_PRICE).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_gbsm_black_76() {
+        let p = SAMPLE_PARAMS;
+        let price = gbsm_price(OptionType::Call, p.s0, p.x, p.t, p.r, 0.0, p.sigma);
+        // Black-76 discounts the undiscounted Black-Scholes price of a zero drift underlying
+        let e_price = (-p.r * p.t).exp() * euro_call(p.s0, p.x, p.t, 0.0, 0.0, p.sigma);
+        let abs = (price - e_price).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_gbsm_garman_kohlhagen() {
+        let p = SAMPLE_PARAMS;
+        let foreign_rate = 0.04;
+        let price = gbsm_price(
+            OptionType::Put,
+            p.s0,
+            p.x,
+            p.t,
+            p.r,
+            p.r - foreign_rate,
+            p.sigma,
         );
+        let e_price = euro_put(p.s0, p.x, p.t, p.r, foreign_rate, p.sigma);
+        let abs = (price - e_price).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_price_dispatch() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert_eq!(
+            price(OptionType::Call, s, x, t, r, q, v),
+            euro_call(s, x, t, r, q, v)
+        );
+        assert_eq!(
+            price(OptionType::Put, s, x, t, r, q, v),
+            euro_put(s, x, t, r, q, v)
+        );
+    }
+
+    #[test]
+    fn test_bachelier_price() {
+        let (forward, t, r, sigma) = (0.03, 2.0, 0.02, 0.01);
+        let call = |x: f64| bachelier_price(OptionType::Call, forward, x, t, r, sigma);
+        let put = |x: f64| bachelier_price(OptionType::Put, forward, x, t, r, sigma);
+        // at the money both are worth the discounted sd / sqrt(2 pi)
+        let atm = E.powf(-r * t) * sigma * t.sqrt() / (2.0 * std::f64::consts::PI).sqrt();
+        assert!((call(forward) - atm).abs() < 1e-15);
+        assert!((put(forward) - atm).abs() < 1e-15);
+        // put-call parity holds for negative strikes too
+        for &x in [-0.01, 0.0, 0.025, 0.05].iter() {
+            let parity = E.powf(-r * t) * (forward - x);
+            assert!((call(x) - put(x) - parity).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_model_comparison() {
+        let (f, t, r, sigma_ln) = (100.0, 0.5, 0.03, 0.2);
+        let compare = |x: f64| model_comparison(f, x, t, r, sigma_ln, sigma_ln * f);
+        // at the money the matched volatilities give nearly the same price
+        let (black, bachelier) = compare(f);
+        assert!((black - bachelier).abs() < 1e-3 * black);
+        // far out of the money the lognormal tail is fatter on the upside
+        let (black, bachelier) = compare(150.0);
+        assert!(black > 2.0 * bachelier);
+    }
+
+    #[test]
+    fn test_price_auto() {
+        let p = SAMPLE_PARAMS;
+        let spec = |asset_class: AssetClass, kind: OptionType| InstrumentSpec {
+            asset_class,
+            kind,
+            s0: p.s0,
+            x: p.x,
+            t: p.t,
+            r: p.r,
+            sigma: p.sigma,
+        };
+        let (s, x, t, r, v) = (p.s0, p.x, p.t, p.r, p.sigma);
+
+        let equity = price_auto(spec(AssetClass::Equity { q: p.q }, OptionType::Call));
+        assert!((equity - E_EURO_CALL_PRICE).abs() < 0.001);
+        assert_eq!(equity, euro_call(s, x, t, r, p.q, v));
+
+        let fx = price_auto(spec(AssetClass::Fx { rf: 0.04 }, OptionType::Put));
+        assert_eq!(fx, euro_put(s, x, t, r, 0.04, v));
+
+        // Black-76 discounts the undiscounted price of a zero drift underlying
+        let future = price_auto(spec(AssetClass::Future, OptionType::Call));
+        let e_future = E.powf(-r * t) * euro_call(s, x, t, 0.0, 0.0, v);
+        assert!((future - e_future).abs() < 1e-12);
+
+        let rate = InstrumentSpec {
+            s0: 0.031,
+            x: 0.03,
+            sigma: 0.008,
+            ..spec(AssetClass::Rate, OptionType::Call)
+        };
+        assert_eq!(
+            price_auto(rate),
+            bachelier_price(OptionType::Call, 0.031, 0.03, t, r, 0.008)
+        );
+    }
+
+    #[test]
+    fn test_euro_put_with_default_no_hazard() {
+        let p = SAMPLE_PARAMS;
+        let price = euro_put_with_default(p.s0, p.x, p.t, p.r, p.q, p.sigma, 0.0);
         let abs = (price - E_EURO_PUT_PRICE).abs();
         assert!(abs < 0.001);
     }
+
+    #[test]
+    fn test_with_default_parity() {
+        let p = SAMPLE_PARAMS;
+        let hazard = 0.2;
+        let call = euro_call_with_default(p.s0, p.x, p.t, p.r, p.q, p.sigma, hazard);
+        let put = euro_put_with_default(p.s0, p.x, p.t, p.r, p.q, p.sigma, hazard);
+        // default risk is priced into both legs, so put-call parity still holds
+        let forward = p.s0 * (-p.q * p.t).exp() - p.x * (-p.r * p.t).exp();
+        assert!((call - put - forward).abs() < 0.001);
+        assert!(put > E_EURO_PUT_PRICE);
+    }
 }
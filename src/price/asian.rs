@@ -0,0 +1,80 @@
+// Module containing pricing of geometric average rate (Asian) options
+// using the Kemna-Vorst closed form for a continuously sampled average, which prices the option
+// under the generalized Black-Scholes-Merton model with an adjusted volatility and cost of carry
+use common::*;
+use price::gbsm_price;
+
+/// Calculates the volatility of the continuously sampled geometric average, `sigma / sqrt(3)`
+///
+/// # Arguments
+/// * `sigma` - volatility of the underlying
+pub fn geometric_asian_vol(sigma: f64) -> f64 {
+    sigma / 3f64.sqrt()
+}
+
+/// Calculates the cost of carry of the continuously sampled geometric average, `(r - q - sigma^2 / 6) / 2`
+///
+/// # Arguments
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility of the underlying
+pub fn geometric_asian_carry(r: f64, q: f64, sigma: f64) -> f64 {
+    0.5 * (r - q - sigma.powf(2.0) / 6.0)
+}
+
+/// Evaluates the price of a European call on the continuously sampled geometric average of the underlying
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn geometric_asian_call(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let b = geometric_asian_carry(r, q, sigma);
+    gbsm_price(OptionType::Call, s0, x, t, r, b, geometric_asian_vol(sigma))
+}
+
+/// Evaluates the price of a European put on the continuously sampled geometric average of the underlying
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn geometric_asian_put(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let b = geometric_asian_carry(r, q, sigma);
+    gbsm_price(OptionType::Put, s0, x, t, r, b, geometric_asian_vol(sigma))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use price::*;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const E_ASIAN_CALL_PRICE: f64 = 1.6881;
+    const E_ASIAN_PUT_PRICE: f64 = 2.1065;
+
+    #[test]
+    fn test_geometric_asian_call() {
+        let p = SAMPLE_PARAMS;
+        let price = geometric_asian_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let abs = (price - E_ASIAN_CALL_PRICE).abs();
+        assert!(abs < 0.001);
+        // averaging dampens volatility, so the Asian is cheaper than the vanilla
+        let vanilla = euro_call(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!(price < vanilla);
+    }
+
+    #[test]
+    fn test_geometric_asian_put() {
+        let p = SAMPLE_PARAMS;
+        let price = geometric_asian_put(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let abs = (price - E_ASIAN_PUT_PRICE).abs();
+        assert!(abs < 0.001);
+    }
+}
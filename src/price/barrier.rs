@@ -0,0 +1,145 @@
+// Module containing pricing of continuously monitored knock-out barrier options
+// using the Merton / Reiner-Rubinstein closed forms, as the vanilla less the matching knock-in
+use std::f64::consts::E;
+
+use price::{euro_call, euro_put};
+use stats::cnd;
+
+// Exponent and reflected 'y' shared by the knock-in formulas
+fn reflection(s0: f64, x: f64, barrier: f64, t: f64, r: f64, q: f64, sigma: f64) -> (f64, f64) {
+    let lambda = (r - q + sigma.powf(2.0) / 2.0) / sigma.powf(2.0);
+    let y = (barrier.powf(2.0) / (s0 * x)).ln() / (sigma * t.sqrt()) + lambda * sigma * t.sqrt();
+    (lambda, y)
+}
+
+/// Evaluates the price of a down-and-out call, a call that dies if the underlying touches a barrier below the strike
+///
+/// Worth nothing once the underlying is at or below the barrier.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `barrier` - The knock-out level, at or below the strike
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+///
+/// # Panics
+/// If `barrier` is above the strike, where the closed form used here does not apply
+pub fn down_and_out_call(s0: f64, x: f64, barrier: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    assert!(
+        barrier <= x,
+        "barrier = {} must be at or below the strike {}",
+        barrier,
+        x
+    );
+    if s0 <= barrier {
+        return 0.0;
+    }
+    let (lambda, y) = reflection(s0, x, barrier, t, r, q, sigma);
+    let ratio = barrier / s0;
+    let knock_in = s0 * E.powf(-q * t) * ratio.powf(2.0 * lambda) * cnd(y)
+        - x * E.powf(-r * t) * ratio.powf(2.0 * lambda - 2.0) * cnd(y - sigma * t.sqrt());
+    euro_call(s0, x, t, r, q, sigma) - knock_in
+}
+
+/// Evaluates the price of an up-and-out put, a put that dies if the underlying touches a barrier above the strike
+///
+/// Worth nothing once the underlying is at or above the barrier.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `barrier` - The knock-out level, at or above the strike
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+///
+/// # Panics
+/// If `barrier` is below the strike, where the closed form used here does not apply
+pub fn up_and_out_put(s0: f64, x: f64, barrier: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    assert!(
+        barrier >= x,
+        "barrier = {} must be at or above the strike {}",
+        barrier,
+        x
+    );
+    if s0 >= barrier {
+        return 0.0;
+    }
+    let (lambda, y) = reflection(s0, x, barrier, t, r, q, sigma);
+    let ratio = barrier / s0;
+    let knock_in = -s0 * E.powf(-q * t) * ratio.powf(2.0 * lambda) * cnd(-y)
+        + x * E.powf(-r * t) * ratio.powf(2.0 * lambda - 2.0) * cnd(-y + sigma * t.sqrt());
+    euro_put(s0, x, t, r, q, sigma) - knock_in
+}
+
+#[cfg(test)]
+mod tests {
+
+    use price::*;
+
+    const STRIKE: f64 = 100.0;
+    const VOL: f64 = 0.25;
+    const INTEREST_RATE: f64 = 0.05;
+    const DIV_YIELD: f64 = 0.01;
+    const TIME_TO_EXPIRY: f64 = 0.5;
+
+    #[test]
+    fn test_down_and_out_call() {
+        let price = |s0: f64, barrier: f64| {
+            down_and_out_call(
+                s0,
+                STRIKE,
+                barrier,
+                TIME_TO_EXPIRY,
+                INTEREST_RATE,
+                DIV_YIELD,
+                VOL,
+            )
+        };
+        let vanilla = euro_call(100.0, STRIKE, TIME_TO_EXPIRY, INTEREST_RATE, DIV_YIELD, VOL);
+        // a distant barrier leaves the vanilla, a closer one takes value away down to nothing at the barrier
+        assert!((price(100.0, 1.0) - vanilla).abs() < 1e-9);
+        assert!(price(100.0, 90.0) < price(100.0, 80.0));
+        assert!(price(100.0, 90.0) < vanilla);
+        assert!(price(90.0 + 1e-6, 90.0).abs() < 1e-5);
+        assert_eq!(price(85.0, 90.0), 0.0);
+    }
+
+    #[test]
+    fn test_up_and_out_put() {
+        let price = |s0: f64, barrier: f64| {
+            up_and_out_put(
+                s0,
+                STRIKE,
+                barrier,
+                TIME_TO_EXPIRY,
+                INTEREST_RATE,
+                DIV_YIELD,
+                VOL,
+            )
+        };
+        let vanilla = euro_put(100.0, STRIKE, TIME_TO_EXPIRY, INTEREST_RATE, DIV_YIELD, VOL);
+        assert!((price(100.0, 10_000.0) - vanilla).abs() < 1e-9);
+        assert!(price(100.0, 110.0) < price(100.0, 130.0));
+        assert!(price(110.0 - 1e-6, 110.0).abs() < 1e-5);
+        assert_eq!(price(115.0, 110.0), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be at or below the strike")]
+    fn test_down_and_out_call_barrier_above_strike() {
+        down_and_out_call(
+            100.0,
+            STRIKE,
+            110.0,
+            TIME_TO_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+    }
+}
@@ -0,0 +1,147 @@
+// Module containing pricing of compound options (options on options)
+// using Geske's closed form, which needs the cumulative bivariate normal distribution
+use std::f64::consts::E;
+
+use common::*;
+use price::euro_call;
+use solvers::bisect;
+use stats::{cbnd, cnd};
+
+/// Evaluates the price of a European call on a European call (Geske)
+///
+/// At `t1` the holder may pay `x1` to receive a call struck at `x2` expiring at `t2`.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x1` - The strike price of the compound option, paid for the underlying call
+/// * `x2` - The strike price of the underlying call
+/// * `t1` - time to expiration of the compound option as a percentage of the year
+/// * `t2` - time to expiration of the underlying call as a percentage of the year, after `t1`
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+#[allow(clippy::too_many_arguments)]
+pub fn call_on_call(
+    s0: f64,
+    x1: f64,
+    x2: f64,
+    t1: f64,
+    t2: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> f64 {
+    let critical = critical_spot(x1, x2, t2 - t1, r, q, sigma);
+    let rho = (t1 / t2).sqrt();
+
+    let y1 = d1(s0, critical, t1, r, q, sigma);
+    let y2 = d2_d1(t1, sigma, y1);
+    let z1 = d1(s0, x2, t2, r, q, sigma);
+    let z2 = d2_d1(t2, sigma, z1);
+
+    s0 * E.powf(-q * t2) * cbnd(z1, y1, rho)
+        - x2 * E.powf(-r * t2) * cbnd(z2, y2, rho)
+        - x1 * E.powf(-r * t1) * cnd(y2)
+}
+
+// Spot at which the underlying call is worth exactly the compound strike at the first expiry
+fn critical_spot(x1: f64, x2: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let mut hi = x1 + x2;
+    while euro_call(hi, x2, t, r, q, sigma) < x1 {
+        hi *= 2.0;
+    }
+    bisect(|s| euro_call(s, x2, t, r, q, sigma) - x1, 0.0, hi, 0.0)
+        .expect("the call is worth nothing at zero spot and at least x1 at the upper bound")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::f64::consts::PI;
+
+    use price::*;
+
+    const UNDERLYING: f64 = 500.0;
+    const COMPOUND_STRIKE: f64 = 50.0;
+    const STRIKE: f64 = 520.0;
+    const FIRST_EXPIRY: f64 = 0.25;
+    const SECOND_EXPIRY: f64 = 0.5;
+    const INTEREST_RATE: f64 = 0.08;
+    const DIV_YIELD: f64 = 0.03;
+    const VOL: f64 = 0.35;
+
+    const E_CALL_ON_CALL: f64 = 17.5945;
+
+    #[test]
+    fn test_call_on_call() {
+        let price = call_on_call(
+            UNDERLYING,
+            COMPOUND_STRIKE,
+            STRIKE,
+            FIRST_EXPIRY,
+            SECOND_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+        let abs = (price - E_CALL_ON_CALL).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_call_on_call_matches_expectation() {
+        // discounted expected payoff of the underlying call at the first expiry, by Simpson's rule
+        let remaining = SECOND_EXPIRY - FIRST_EXPIRY;
+        let drift = (INTEREST_RATE - DIV_YIELD - 0.5 * VOL * VOL) * FIRST_EXPIRY;
+        let integrand = |z: f64| {
+            let s_t = UNDERLYING * (drift + VOL * FIRST_EXPIRY.sqrt() * z).exp();
+            let call = euro_call(s_t, STRIKE, remaining, INTEREST_RATE, DIV_YIELD, VOL);
+            (call - COMPOUND_STRIKE).max(0.0) * (-0.5 * z * z).exp() / (2.0 * PI).sqrt()
+        };
+        let (lo, hi, steps) = (-10.0, 10.0, 20000);
+        let h = (hi - lo) / steps as f64;
+        let mut sum = integrand(lo) + integrand(hi);
+        for i in 1..steps {
+            let weight = if i % 2 == 0 { 2.0 } else { 4.0 };
+            sum += weight * integrand(lo + i as f64 * h);
+        }
+        let expected = (-INTEREST_RATE * FIRST_EXPIRY).exp() * sum * h / 3.0;
+
+        let price = call_on_call(
+            UNDERLYING,
+            COMPOUND_STRIKE,
+            STRIKE,
+            FIRST_EXPIRY,
+            SECOND_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+        let abs = (price - expected).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_call_on_call_free_strike() {
+        // with nothing to pay at the first expiry the compound is just the underlying call
+        let price = call_on_call(
+            UNDERLYING,
+            1e-9,
+            STRIKE,
+            FIRST_EXPIRY,
+            SECOND_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+        let call = euro_call(
+            UNDERLYING,
+            STRIKE,
+            SECOND_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+        assert!((price - call).abs() < 0.001);
+    }
+}
@@ -0,0 +1,138 @@
+// Module containing pricing of floating strike lookback options
+// using the Goldman-Sosin-Gatto closed form for a continuously monitored extremum
+use std::f64::consts::E;
+
+use common::*;
+use stats::{cnd, pdf};
+
+/// Evaluates the price of a floating strike lookback call, paying the terminal price less the minimum observed price
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `s_min` - The minimum underlying price observed so far, `s0` for a new option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn lookback_floating_call(s0: f64, s_min: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let b = r - q;
+    let a1 = d1(s0, s_min, t, r, q, sigma);
+    let a2 = d2_d1(t, sigma, a1);
+    let vanilla = s0 * E.powf(-q * t) * cnd(a1) - s_min * E.powf(-r * t) * cnd(a2);
+
+    let extremum = if b.abs() < 1e-9 {
+        // limit of the term below as the carry goes to zero
+        let density = pdf(a1);
+        sigma * t.sqrt() * (density - a1 * cnd(-a1))
+    } else {
+        let power = (s0 / s_min).powf(-2.0 * b / sigma.powf(2.0));
+        let shift = 2.0 * b * t.sqrt() / sigma;
+        sigma.powf(2.0) / (2.0 * b) * (power * cnd(-a1 + shift) - E.powf(b * t) * cnd(-a1))
+    };
+    vanilla + s0 * E.powf(-r * t) * extremum
+}
+
+/// Evaluates the price of a floating strike lookback put, paying the maximum observed price less the terminal price
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `s_max` - The maximum underlying price observed so far, `s0` for a new option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn lookback_floating_put(s0: f64, s_max: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let b = r - q;
+    let b1 = d1(s0, s_max, t, r, q, sigma);
+    let b2 = d2_d1(t, sigma, b1);
+    let vanilla = s_max * E.powf(-r * t) * cnd(-b2) - s0 * E.powf(-q * t) * cnd(-b1);
+
+    let extremum = if b.abs() < 1e-9 {
+        // limit of the term below as the carry goes to zero
+        let density = pdf(b1);
+        sigma * t.sqrt() * (density + b1 * cnd(b1))
+    } else {
+        let power = (s0 / s_max).powf(-2.0 * b / sigma.powf(2.0));
+        let shift = 2.0 * b * t.sqrt() / sigma;
+        sigma.powf(2.0) / (2.0 * b) * (-power * cnd(b1 - shift) + E.powf(b * t) * cnd(b1))
+    };
+    vanilla + s0 * E.powf(-r * t) * extremum
+}
+
+#[cfg(test)]
+mod tests {
+
+    use price::*;
+
+    const UNDERLYING: f64 = 120.0;
+    const VOL: f64 = 0.30;
+    const INTEREST_RATE: f64 = 0.10;
+    const DIV_YIELD: f64 = 0.0;
+    const TIME_TO_EXPIRY: f64 = 0.5;
+
+    const E_LOOKBACK_CALL: f64 = 28.2133;
+    const E_LOOKBACK_PUT: f64 = 28.8011;
+
+    #[test]
+    fn test_lookback_floating_call() {
+        let price = lookback_floating_call(
+            UNDERLYING,
+            100.0,
+            TIME_TO_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+        let abs = (price - E_LOOKBACK_CALL).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_lookback_floating_put() {
+        let price = lookback_floating_put(
+            UNDERLYING,
+            150.0,
+            TIME_TO_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+        let abs = (price - E_LOOKBACK_PUT).abs();
+        assert!(abs < 0.001);
+    }
+
+    #[test]
+    fn test_lookback_zero_carry() {
+        // the zero carry limit joins up with a tiny non-zero carry
+        let call = lookback_floating_call(100.0, 90.0, 0.5, 0.05, 0.05, VOL);
+        let call_near = lookback_floating_call(100.0, 90.0, 0.5, 0.05, 0.05 - 1e-7, VOL);
+        let put = lookback_floating_put(100.0, 110.0, 0.5, 0.05, 0.05, VOL);
+        let put_near = lookback_floating_put(100.0, 110.0, 0.5, 0.05, 0.05 - 1e-7, VOL);
+        assert!((call - 17.1186).abs() < 0.001);
+        assert!((call - call_near).abs() < 0.0001);
+        assert!((put - 19.5663).abs() < 0.001);
+        assert!((put - put_near).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_lookback_exceeds_vanilla() {
+        // a new lookback call is at least an at-the-money call
+        let lookback = lookback_floating_call(
+            UNDERLYING,
+            UNDERLYING,
+            TIME_TO_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+        let vanilla = euro_call(
+            UNDERLYING,
+            UNDERLYING,
+            TIME_TO_EXPIRY,
+            INTEREST_RATE,
+            DIV_YIELD,
+            VOL,
+        );
+        assert!(lookback > vanilla);
+    }
+}
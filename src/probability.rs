@@ -0,0 +1,88 @@
+// Module containing risk-neutral probabilities of the underlying's path and terminal value
+use common::*;
+use stats::cnd;
+
+/// Calculates the risk-neutral probability that an option finishes in the money
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn prob_itm(kind: OptionType, s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    let d2 = d2(s0, x, t, r, q, sigma);
+    match kind {
+        OptionType::Call => cnd(d2),
+        OptionType::Put => cnd(-d2),
+    }
+}
+
+/// Calculates the risk-neutral probability that the underlying touches a level at any time before expiry
+///
+/// Uses the reflection principle for the first passage of geometric Brownian motion, with log drift
+/// `nu = r - q - sigma^2 / 2`. Works for levels above and below the current spot.
+///
+/// # Arguments
+/// * `s0` - The underlying price
+/// * `barrier` - The level to be touched
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn prob_of_touch(s0: f64, barrier: f64, t: f64, r: f64, q: f64, sigma: f64) -> f64 {
+    if barrier == s0 {
+        return 1.0;
+    }
+    let nu = r - q - sigma.powf(2.0) / 2.0;
+    let distance = (barrier / s0).ln();
+    let sd = sigma * t.sqrt();
+    let reflection = (barrier / s0).powf(2.0 * nu / sigma.powf(2.0));
+    let prob = if barrier > s0 {
+        cnd((-distance + nu * t) / sd) + reflection * cnd((-distance - nu * t) / sd)
+    } else {
+        cnd((distance - nu * t) / sd) + reflection * cnd((distance + nu * t) / sd)
+    };
+    prob.min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use probability::*;
+    use stats::cnd;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_prob_of_touch_exceeds_itm() {
+        let p = SAMPLE_PARAMS;
+        for &(barrier, kind) in [(70.0, OptionType::Call), (58.0, OptionType::Put)].iter() {
+            let touch = prob_of_touch(p.s0, barrier, p.t, p.r, p.q, p.sigma);
+            let itm = prob_itm(kind, p.s0, barrier, p.t, p.r, p.q, p.sigma);
+            assert!(touch > itm);
+            // without drift a touch is almost exactly twice as likely as finishing beyond the level
+            assert!((touch / itm - 2.0).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn test_prob_of_touch_driftless() {
+        let p = SAMPLE_PARAMS;
+        // with zero log drift the reflection principle gives exactly 2 * N(-|ln(H/S)| / (sigma sqrt(t)))
+        let r = 0.5 * p.sigma * p.sigma;
+        let touch = prob_of_touch(p.s0, 75.0, p.t, r, 0.0, p.sigma);
+        let sd = p.sigma * p.t.sqrt();
+        let expected = 2.0 * cnd(-(75.0_f64 / p.s0).ln() / sd);
+        assert!((touch - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_prob_of_touch_at_spot() {
+        let p = SAMPLE_PARAMS;
+        let touch = prob_of_touch(p.s0, p.s0, p.t, p.r, p.q, p.sigma);
+        assert_eq!(touch, 1.0);
+    }
+}
@@ -0,0 +1,179 @@
+// Module containing conversions between the strike, delta and price of an option
+use common::OptionType;
+use greeks::{delta, strike_for_delta_call, strike_for_delta_put};
+use price::price;
+use solvers::bisect;
+
+/// Market inputs shared by every option quoted on one expiry, with a single volatility
+///
+/// Desks quote options by strike, by delta or by premium. With the volatility held fixed each
+/// representation determines the others, and the methods convert between them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    /// The underlying price
+    pub s0: f64,
+    /// time to expiration as a percentage of the year
+    pub t: f64,
+    /// continuously compounded risk-free interest rate
+    pub r: f64,
+    /// continuously compounded divident yield
+    pub q: f64,
+    /// volatility
+    pub sigma: f64,
+}
+
+impl Quote {
+    /// Calculates the delta of the option at a strike
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `x` - The strike price of the option
+    pub fn strike_to_delta(&self, kind: OptionType, x: f64) -> f64 {
+        delta(kind, self.s0, x, self.t, self.r, self.q, self.sigma)
+    }
+
+    /// Calculates the strike of the option with a given delta
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `delta` - The delta of the option, negative for puts
+    pub fn delta_to_strike(&self, kind: OptionType, delta: f64) -> f64 {
+        match kind {
+            OptionType::Call => {
+                strike_for_delta_call(self.s0, delta, self.t, self.r, self.q, self.sigma)
+            }
+            OptionType::Put => {
+                strike_for_delta_put(self.s0, delta, self.t, self.r, self.q, self.sigma)
+            }
+        }
+    }
+
+    /// Calculates the price of the option at a strike
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `x` - The strike price of the option
+    pub fn strike_to_price(&self, kind: OptionType, x: f64) -> f64 {
+        price(kind, self.s0, x, self.t, self.r, self.q, self.sigma)
+    }
+
+    /// Calculates the strike at which the option has a given price
+    ///
+    /// Prices fall with the strike for calls and rise for puts, so the strike is found by bisection in log strike.
+    /// Returns `None` when the price lies outside what strikes between `s0 / 1000` and `s0 * 1000` can produce.
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `price` - The price of the option
+    pub fn price_to_strike(&self, kind: OptionType, price: f64) -> Option<f64> {
+        let (lo, hi) = ((self.s0 / 1000.0).ln(), (self.s0 * 1000.0).ln());
+        let diff = |log_x: f64| self.strike_to_price(kind, log_x.exp()) - price;
+        bisect(diff, lo, hi, 0.0).map(f64::exp)
+    }
+
+    /// Calculates the price of the option with a given delta
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `delta` - The delta of the option, negative for puts
+    pub fn delta_to_price(&self, kind: OptionType, delta: f64) -> f64 {
+        self.strike_to_price(kind, self.delta_to_strike(kind, delta))
+    }
+
+    /// Calculates the delta of the option with a given price
+    ///
+    /// # Arguments
+    /// * `kind` - Whether the option is a call or a put
+    /// * `price` - The price of the option
+    pub fn price_to_delta(&self, kind: OptionType, price: f64) -> Option<f64> {
+        self.price_to_strike(kind, price)
+            .map(|x| self.strike_to_delta(kind, x))
+    }
+}
+
+/// Calculates the price of an option struck by delta, as quoted in crypto OTC markets
+///
+/// Solves for the strike with the requested delta at the given volatility and prices the option there, the free
+/// function form of `Quote::delta_to_price`.
+///
+/// # Arguments
+/// * `kind` - Whether the option is a call or a put
+/// * `s0` - The underlying price of the option
+/// * `target_delta` - The delta of the option, negative for puts
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn price_from_delta(
+    kind: OptionType,
+    s0: f64,
+    target_delta: f64,
+    t: f64,
+    r: f64,
+    q: f64,
+    sigma: f64,
+) -> f64 {
+    Quote { s0, t, r, q, sigma }.delta_to_price(kind, target_delta)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use price::{euro_call, euro_put};
+    use quote::*;
+    use test_fixtures::SAMPLE_PARAMS;
+
+    const QUOTE: Quote = Quote {
+        s0: SAMPLE_PARAMS.s0,
+        t: SAMPLE_PARAMS.t,
+        r: SAMPLE_PARAMS.r,
+        q: SAMPLE_PARAMS.q,
+        sigma: SAMPLE_PARAMS.sigma,
+    };
+
+    #[test]
+    fn test_quote_round_trip_from_strike() {
+        for &kind in [OptionType::Call, OptionType::Put].iter() {
+            for &x in [55.0, 65.0, 75.0].iter() {
+                let delta = QUOTE.strike_to_delta(kind, x);
+                assert!((QUOTE.delta_to_strike(kind, delta) - x).abs() < 1e-4);
+
+                let price = QUOTE.strike_to_price(kind, x);
+                assert!((QUOTE.price_to_strike(kind, price).unwrap() - x).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quote_round_trip_from_delta() {
+        for &(kind, delta) in [(OptionType::Call, 0.25), (OptionType::Put, -0.25)].iter() {
+            let price = QUOTE.delta_to_price(kind, delta);
+            assert!((QUOTE.price_to_delta(kind, price).unwrap() - delta).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_price_from_delta() {
+        let p = SAMPLE_PARAMS;
+        let x = 72.0;
+        for &kind in [OptionType::Call, OptionType::Put].iter() {
+            let delta = QUOTE.strike_to_delta(kind, x);
+            let price = price_from_delta(kind, p.s0, delta, p.t, p.r, p.q, p.sigma);
+            let expected = match kind {
+                OptionType::Call => euro_call(p.s0, x, p.t, p.r, p.q, p.sigma),
+                OptionType::Put => euro_put(p.s0, x, p.t, p.r, p.q, p.sigma),
+            };
+            assert!((price - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_quote_price_out_of_range() {
+        let p = SAMPLE_PARAMS;
+        // a call can never be worth more than the underlying
+        assert!(QUOTE
+            .price_to_strike(OptionType::Call, 2.0 * p.s0)
+            .is_none());
+    }
+}
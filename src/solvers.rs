@@ -0,0 +1,213 @@
+// Module containing one dimensional root finders shared by the solvers of the crate
+
+// Iteration cap of the bracketing solvers, far more than needed to shrink any bracket to machine precision
+const MAX_ITERATIONS: usize = 200;
+
+/// Finds a root of `f` by Newton-Raphson iteration from a starting guess
+///
+/// Converges when `|f(x)| < tol`. Returns `None` if that takes more than `max_iter` steps, or if the derivative
+/// vanishes or either function leaves the real numbers, which lets callers reject parts of the domain by returning
+/// `NaN` there.
+///
+/// # Arguments
+/// * `f` - The function whose root is sought
+/// * `df` - The derivative of `f`
+/// * `x0` - The starting guess
+/// * `tol` - Largest `|f(x)|` accepted as a root
+/// * `max_iter` - Maximum number of Newton steps
+pub fn newton(
+    f: impl Fn(f64) -> f64,
+    df: impl Fn(f64) -> f64,
+    x0: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Option<f64> {
+    let mut x = x0;
+    for _ in 0..max_iter {
+        let fx = f(x);
+        if !fx.is_finite() {
+            return None;
+        }
+        if fx.abs() < tol {
+            return Some(x);
+        }
+        let slope = df(x);
+        if slope == 0.0 || !slope.is_finite() {
+            return None;
+        }
+        x -= fx / slope;
+    }
+    None
+}
+
+/// Finds a root of `f` between two bounds by bisection
+///
+/// Halves the bracket until it is narrower than `2 * tol` and returns its midpoint. Returns `None` when `f` has
+/// the same sign at both bounds.
+///
+/// # Arguments
+/// * `f` - The function whose root is sought
+/// * `a` - One end of the bracket
+/// * `b` - The other end of the bracket
+/// * `tol` - Largest distance from the root accepted, zero to run to machine precision
+pub fn bisect(f: impl Fn(f64) -> f64, a: f64, b: f64, tol: f64) -> Option<f64> {
+    let (fa, fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if fa.signum() == fb.signum() {
+        return None;
+    }
+    let (mut lo, mut hi) = (a, b);
+    for _ in 0..MAX_ITERATIONS {
+        let mid = (lo + hi) / 2.0;
+        if (hi - lo).abs() / 2.0 < tol {
+            return Some(mid);
+        }
+        if f(mid).signum() == fa.signum() {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some((lo + hi) / 2.0)
+}
+
+/// Finds a root of `f` between two bounds with Brent's method
+///
+/// Combines inverse quadratic interpolation and secant steps with bisection, so it converges superlinearly on
+/// smooth functions while keeping the guarantee of bisection. Prefer it when `f` is expensive. Returns `None` when
+/// `f` has the same sign at both bounds.
+///
+/// # Arguments
+/// * `f` - The function whose root is sought
+/// * `a` - One end of the bracket
+/// * `b` - The other end of the bracket
+/// * `tol` - Largest distance from the root accepted, zero to run to machine precision
+pub fn brent(f: impl Fn(f64) -> f64, a: f64, b: f64, tol: f64) -> Option<f64> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if fa.signum() == fb.signum() {
+        return None;
+    }
+    // 'b' is the best estimate, 'c' the other end of the bracket and 'a' the previous estimate
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+    for _ in 0..MAX_ITERATIONS {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let xm = 0.5 * (c - b);
+        if xm.abs() <= tol1 || fb == 0.0 {
+            return Some(b);
+        }
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            // attempt interpolation, secant when only two points are distinct
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * xm * s, 1.0 - s)
+            } else {
+                let (qa, rb) = (fa / fc, fb / fc);
+                (
+                    s * (2.0 * xm * qa * (qa - rb) - (b - a) * (rb - 1.0)),
+                    (qa - 1.0) * (rb - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            let min1 = 3.0 * xm * q - (tol1 * q).abs();
+            let min2 = (e * q).abs();
+            if 2.0 * p < min1.min(min2) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(xm) };
+        fb = f(b);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use solvers::*;
+
+    const SQRT_2: f64 = std::f64::consts::SQRT_2;
+
+    #[test]
+    fn test_newton() {
+        let root = newton(|x| x * x - 2.0, |x| 2.0 * x, 1.0, 1e-12, 50).unwrap();
+        assert!((root - SQRT_2).abs() < 1e-12);
+
+        // a flat start has no Newton step
+        assert_eq!(newton(|x| x * x - 2.0, |x| 2.0 * x, 0.0, 1e-12, 50), None);
+        // nor does a function with no real root converge
+        assert_eq!(newton(|x| x * x + 2.0, |x| 2.0 * x, 1.0, 1e-12, 50), None);
+    }
+
+    #[test]
+    fn test_bisect() {
+        let root = bisect(|x| x * x - 2.0, 0.0, 2.0, 1e-12).unwrap();
+        assert!((root - SQRT_2).abs() < 1e-12);
+        // the bracket may be given in either order
+        let reversed = bisect(|x| x * x - 2.0, 2.0, 0.0, 1e-12).unwrap();
+        assert!((reversed - SQRT_2).abs() < 1e-12);
+        assert_eq!(bisect(|x| x * x - 2.0, 2.0, 3.0, 1e-12), None);
+        assert_eq!(bisect(|x| x - 1.0, 1.0, 3.0, 1e-12), Some(1.0));
+    }
+
+    #[test]
+    fn test_brent() {
+        let root = brent(|x| x * x - 2.0, 0.0, 2.0, 1e-12).unwrap();
+        assert!((root - SQRT_2).abs() < 1e-12);
+        let cubic = brent(|x| x.powf(3.0) - 2.0 * x - 5.0, 2.0, 3.0, 1e-12).unwrap();
+        assert!((cubic.powf(3.0) - 2.0 * cubic - 5.0).abs() < 1e-10);
+        assert_eq!(brent(|x| x * x - 2.0, 2.0, 3.0, 1e-12), None);
+
+        // far fewer evaluations than bisection
+        let calls = std::cell::Cell::new(0);
+        brent(
+            |x| {
+                calls.set(calls.get() + 1);
+                x.exp() - 3.0
+            },
+            0.0,
+            5.0,
+            1e-12,
+        )
+        .unwrap();
+        assert!(calls.get() < 20);
+    }
+}
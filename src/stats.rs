@@ -1,5 +1,6 @@
 // Helper module containing statistics functions
-use std::f64::consts::E;
+use rand_core::RngCore;
+use std::f64::consts::{E, PI};
 
 const A1: f64 = 0.31938153;
 const A2: f64 = -0.356563782;
@@ -8,13 +9,611 @@ const A4: f64 = -1.821255978;
 const A5: f64 = 1.330274429;
 const RSQRTPI: f64 = 0.39894228040143267793994605993438;
 
-// Simple implementation of cumulative normal distribution for a provided 'x'
+/// Accuracy of the cumulative normal distribution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CndPrecision {
+    /// Abramowitz-Stegun polynomial, absolute error up to ~7.5e-8
+    Fast,
+    /// Chebyshev fit of the complementary error function, close to machine precision including the tails
+    Accurate,
+}
+
+// Cumulative normal distribution for a provided 'x', from the complementary error function
+// so that it stays accurate far into the tails
 pub fn cnd(x: f64) -> f64 {
+    0.5 * erfc(-x / 2f64.sqrt())
+}
+
+// Abramowitz-Stegun polynomial approximation of the cumulative normal distribution for a provided 'x'
+fn cnd_poly(x: f64) -> f64 {
     let k = 1.0 / (1.0 + 0.2316419 * x.abs());
-    let mut cnd: f64 =
-        RSQRTPI * E.powf(-0.5 * x * x) * (k * (A1 + k * (A2 + k * (A3 + k * (A4 + k * A5)))));
+    let mut cnd: f64 = pdf(x) * (k * (A1 + k * (A2 + k * (A3 + k * (A4 + k * A5)))));
     if x > 0.0 {
         cnd = 1.0 - cnd;
     }
     return cnd;
 }
+
+// Standard normal probability density function for a provided 'x'
+pub fn pdf(x: f64) -> f64 {
+    RSQRTPI * E.powf(-0.5 * x * x)
+}
+
+// Cumulative normal distribution for a provided 'x' at the requested precision
+pub fn cnd_with(x: f64, precision: CndPrecision) -> f64 {
+    match precision {
+        CndPrecision::Fast => cnd_poly(x),
+        CndPrecision::Accurate => cnd(x),
+    }
+}
+
+// Chebyshev coefficients of the complementary error function (Numerical Recipes, 3rd edition)
+const ERFC_COF: [f64; 28] = [
+    -1.3026537197817094,
+    6.419697923564902e-1,
+    1.947647320418584e-2,
+    -9.56151478680863e-3,
+    -9.46595344482036e-4,
+    3.66839497852761e-4,
+    4.2523324806907e-5,
+    -2.0278578112534e-5,
+    -1.624290004647e-6,
+    1.303655835580e-6,
+    1.5626441722e-8,
+    -8.5238095915e-8,
+    6.529054439e-9,
+    5.059343495e-9,
+    -9.91364156e-10,
+    -2.27365122e-10,
+    9.6467911e-11,
+    2.394038e-12,
+    -6.886027e-12,
+    8.94487e-13,
+    3.13092e-13,
+    -1.12708e-13,
+    3.81e-16,
+    7.106e-15,
+    -1.523e-15,
+    -9.4e-17,
+    1.21e-16,
+    -2.8e-17,
+];
+
+// Complementary error function, with relative error around 1.2e-16
+fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+    let t = 2.0 / (2.0 + x);
+    let ty = 4.0 * t - 2.0;
+    let (mut d, mut dd) = (0.0, 0.0);
+    for cof in ERFC_COF[1..].iter().rev() {
+        let tmp = d;
+        d = ty * d - dd + cof;
+        dd = tmp;
+    }
+    t * E.powf(-x * x + 0.5 * (ERFC_COF[0] + ty * d) - dd)
+}
+
+// Gauss-Legendre abscissae and weights for 6, 12 and 20 point rules (half of each symmetric rule)
+const GL_X: [[f64; 10]; 3] = [
+    [
+        -0.932469514203152,
+        -0.661209386466265,
+        -0.238619186083197,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+    ],
+    [
+        -0.981560634246719,
+        -0.904117256370475,
+        -0.769902674194305,
+        -0.587317954286617,
+        -0.36783149899818,
+        -0.125233408511469,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+    ],
+    [
+        -0.993128599185095,
+        -0.963971927277914,
+        -0.912234428251326,
+        -0.839116971822219,
+        -0.746331906460151,
+        -0.636053680726515,
+        -0.510867001950827,
+        -0.37370608871542,
+        -0.227785851141645,
+        -0.0765265211334973,
+    ],
+];
+const GL_W: [[f64; 10]; 3] = [
+    [
+        0.17132449237917,
+        0.360761573048138,
+        0.46791393457269,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+    ],
+    [
+        0.0471753363865118,
+        0.106939325995318,
+        0.160078328543346,
+        0.203167426723066,
+        0.233492536538355,
+        0.249147045813403,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+    ],
+    [
+        0.0176140071391521,
+        0.0406014298003869,
+        0.0626720483341091,
+        0.0832767415767048,
+        0.10193011981724,
+        0.118194531961518,
+        0.131688638449177,
+        0.142096109318382,
+        0.149172986472604,
+        0.152753387130726,
+    ],
+];
+
+// Cumulative bivariate normal distribution P(X < a, Y < b) for standard normals with correlation 'rho'
+// using Genz's (2004) refinement of the Drezner-Wesolowsky algorithm
+pub fn cbnd(a: f64, b: f64, rho: f64) -> f64 {
+    let (rule, points) = if rho.abs() < 0.3 {
+        (0, 3)
+    } else if rho.abs() < 0.75 {
+        (1, 6)
+    } else {
+        (2, 10)
+    };
+
+    let h = -a;
+    let mut k = -b;
+    let mut hk = h * k;
+    let mut bvn = 0.0;
+
+    if rho.abs() < 0.925 {
+        if rho.abs() > 0.0 {
+            let hs = (h * h + k * k) / 2.0;
+            let asr = rho.asin();
+            for i in 0..points {
+                for sign in [-1.0, 1.0].iter() {
+                    let sn = (asr * (sign * GL_X[rule][i] + 1.0) / 2.0).sin();
+                    bvn += GL_W[rule][i] * E.powf((sn * hk - hs) / (1.0 - sn * sn));
+                }
+            }
+            bvn *= asr / (4.0 * PI);
+        }
+        return bvn + cnd(-h) * cnd(-k);
+    }
+
+    if rho < 0.0 {
+        k = -k;
+        hk = -hk;
+    }
+    if rho.abs() < 1.0 {
+        let ass = (1.0 - rho) * (1.0 + rho);
+        let mut a = ass.sqrt();
+        let bs = (h - k).powf(2.0);
+        let c = (4.0 - hk) / 8.0;
+        let d = (12.0 - hk) / 16.0;
+        let asr = -(bs / ass + hk) / 2.0;
+        if asr > -100.0 {
+            bvn = a
+                * E.powf(asr)
+                * (1.0 - c * (bs - ass) * (1.0 - d * bs / 5.0) / 3.0 + c * d * ass * ass / 5.0);
+        }
+        if -hk < 100.0 {
+            let b = bs.sqrt();
+            bvn -= E.powf(-hk / 2.0)
+                * (2.0 * PI).sqrt()
+                * cnd(-b / a)
+                * b
+                * (1.0 - c * bs * (1.0 - d * bs / 5.0) / 3.0);
+        }
+        a /= 2.0;
+        for i in 0..points {
+            for sign in [-1.0, 1.0].iter() {
+                let xs = (a * (sign * GL_X[rule][i] + 1.0)).powf(2.0);
+                let rs = (1.0 - xs).sqrt();
+                let asr = -(bs / xs + hk) / 2.0;
+                if asr > -100.0 {
+                    bvn += a
+                        * GL_W[rule][i]
+                        * E.powf(asr)
+                        * (E.powf(-hk * (1.0 - rs) / (2.0 * (1.0 + rs))) / rs
+                            - (1.0 + c * xs * (1.0 + d * xs)));
+                }
+            }
+        }
+        bvn = -bvn / (2.0 * PI);
+    }
+
+    if rho > 0.0 {
+        bvn + cnd(-h.max(k))
+    } else {
+        let mut bvn = -bvn;
+        if k > h {
+            bvn += cnd(k) - cnd(h);
+        }
+        bvn
+    }
+}
+
+// Rational approximation coefficients of the inverse normal (Acklam)
+const INV_A: [f64; 6] = [
+    -3.969683028665376e+01,
+    2.209460984245205e+02,
+    -2.759285104469687e+02,
+    1.38357751867269e+02,
+    -3.066479806614716e+01,
+    2.506628277459239e+00,
+];
+const INV_B: [f64; 5] = [
+    -5.447609879822406e+01,
+    1.615858368580409e+02,
+    -1.556989798598866e+02,
+    6.680131188771972e+01,
+    -1.328068155288572e+01,
+];
+const INV_C: [f64; 6] = [
+    -7.784894002430293e-03,
+    -3.223964580411365e-01,
+    -2.400758277161838e+00,
+    -2.549732539343734e+00,
+    4.374664141464968e+00,
+    2.938163982698783e+00,
+];
+const INV_D: [f64; 4] = [
+    7.784695709041462e-03,
+    3.224671290700398e-01,
+    2.445134137142996e+00,
+    3.754408661907416e+00,
+];
+
+// Inverse of the cumulative normal distribution, the 'x' with N(x) = 'p', for 'p' in (0, 1)
+// Acklam's rational approximation polished with one Halley step against the accurate cnd
+pub fn inv_cnd(p: f64) -> f64 {
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let p_low = 0.02425;
+    let x = if p < p_low || p > 1.0 - p_low {
+        let q = (-2.0 * p.min(1.0 - p).ln()).sqrt();
+        let x = (((((INV_C[0] * q + INV_C[1]) * q + INV_C[2]) * q + INV_C[3]) * q + INV_C[4]) * q
+            + INV_C[5])
+            / ((((INV_D[0] * q + INV_D[1]) * q + INV_D[2]) * q + INV_D[3]) * q + 1.0);
+        if p < p_low {
+            x
+        } else {
+            -x
+        }
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((INV_A[0] * r + INV_A[1]) * r + INV_A[2]) * r + INV_A[3]) * r + INV_A[4]) * r
+            + INV_A[5])
+            * q
+            / (((((INV_B[0] * r + INV_B[1]) * r + INV_B[2]) * r + INV_B[3]) * r + INV_B[4]) * r
+                + 1.0)
+    };
+    let e = cnd_with(x, CndPrecision::Accurate) - p;
+    let u = e * (2.0 * PI).sqrt() * E.powf(x * x / 2.0);
+    x - u / (1.0 + x * u / 2.0)
+}
+
+// Draws a standard normal variate from 'rng' using the Box-Muller transform
+pub fn sample_standard_normal(rng: &mut impl RngCore) -> f64 {
+    // top 53 bits give a uniform on [0, 1), flipped to (0, 1] so the log is finite
+    let u1 = 1.0 - (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    let u2 = (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+// Annualized Sharpe ratio of per period returns 'pnl_samples', e.g. simulated strategy returns, in excess of the
+// annual 'risk_free' rate spread evenly over 'periods_per_year', using the sample standard deviation. None for
+// fewer than two samples, which have no sample standard deviation
+pub fn strategy_sharpe(pnl_samples: &[f64], risk_free: f64, periods_per_year: f64) -> Option<f64> {
+    if pnl_samples.len() < 2 {
+        return None;
+    }
+    let n = pnl_samples.len() as f64;
+    let mean = pnl_samples.iter().sum::<f64>() / n;
+    let variance = pnl_samples
+        .iter()
+        .map(|s| (s - mean).powf(2.0))
+        .sum::<f64>()
+        / (n - 1.0);
+    Some((mean - risk_free / periods_per_year) / variance.sqrt() * periods_per_year.sqrt())
+}
+
+// Annualized close to close realized volatility over each rolling 'window' of log returns of 'prices', sampled once
+// a day, using the sample standard deviation. Empty when there are fewer than 'window' returns or 'window' < 2
+pub fn rolling_realized_vol(prices: &[f64], window: usize, days_per_year: f64) -> Vec<f64> {
+    if window < 2 {
+        return vec![];
+    }
+    let returns: Vec<f64> = prices.windows(2).map(|p| (p[1] / p[0]).ln()).collect();
+    returns
+        .windows(window)
+        .map(|w| {
+            let n = w.len() as f64;
+            let mean = w.iter().sum::<f64>() / n;
+            let variance = w.iter().map(|r| (r - mean).powf(2.0)).sum::<f64>() / (n - 1.0);
+            (variance * days_per_year).sqrt()
+        })
+        .collect()
+}
+
+// Volatility cone of daily 'prices': for each of 'windows', the 'percentiles' (0 to 100) of the rolling realized
+// volatility over that many returns, interpolated linearly between ranks like numpy's default. Non-finite vols, from
+// a zero or missing price in the window, are skipped, and a window with no finite vols gives a row of NaN.
+// Panics if a percentile is outside 0 to 100
+pub fn vol_cone(
+    prices: &[f64],
+    windows: &[usize],
+    percentiles: &[f64],
+    days_per_year: f64,
+) -> Vec<Vec<f64>> {
+    windows
+        .iter()
+        .map(|&window| {
+            let mut vols: Vec<f64> = rolling_realized_vol(prices, window, days_per_year)
+                .into_iter()
+                .filter(|v| v.is_finite())
+                .collect();
+            vols.sort_by(|a, b| a.total_cmp(b));
+            percentiles
+                .iter()
+                .map(|&p| percentile_sorted(&vols, p))
+                .collect()
+        })
+        .collect()
+}
+
+// Percentile 'p' (0 to 100) of ascending 'sorted' with linear interpolation between ranks. Panics if 'p' is outside
+// 0 to 100
+fn percentile_sorted(sorted: &[f64], p: f64) -> f64 {
+    assert!(
+        (0.0..=100.0).contains(&p),
+        "percentile = {} must be between 0 and 100",
+        p
+    );
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use mc::SplitMix64;
+    use stats::*;
+
+    // (x, N(x)) from an arbitrary precision reference
+    const CND_REFERENCE: [(f64, f64); 10] = [
+        (-8.0, 6.220960574271819e-16),
+        (-5.0, 2.866515718791946e-07),
+        (-3.0, 0.0013498980316300957),
+        (-1.0, 0.15865525393145707),
+        (-0.5, 0.3085375387259869),
+        (0.0, 0.5),
+        (0.3, 0.6179114221889526),
+        (1.0, 0.8413447460685429),
+        (2.5, 0.9937903346742238),
+        (5.0, 0.9999997133484281),
+    ];
+
+    #[test]
+    fn test_cnd_accurate() {
+        for &(x, expected) in CND_REFERENCE.iter() {
+            let relative = (cnd_with(x, CndPrecision::Accurate) - expected).abs() / expected;
+            assert!(relative < 1e-13);
+        }
+    }
+
+    #[test]
+    fn test_pdf() {
+        assert!((pdf(0.0) - 0.3989422804).abs() < 1e-10);
+        assert!((pdf(0.0) - 1.0 / (2.0 * PI).sqrt()).abs() < 1e-15);
+        for &x in [0.5, 1.0, 2.3, 6.0].iter() {
+            assert_eq!(pdf(x), pdf(-x));
+        }
+    }
+
+    #[test]
+    fn test_cnd() {
+        for &(x, expected) in CND_REFERENCE.iter() {
+            assert_eq!(cnd_with(x, CndPrecision::Accurate), cnd(x));
+            assert!((cnd(x) - expected).abs() / expected < 1e-13);
+        }
+        // the polynomial is off by about 4.5e-10 here, some 0.16% of the probability
+        assert!((cnd(-5.0) - 2.866515718791946e-7).abs() < 1e-20);
+    }
+
+    #[test]
+    fn test_cnd_fast_absolute_error() {
+        for &(x, expected) in CND_REFERENCE.iter() {
+            assert!((cnd_with(x, CndPrecision::Fast) - expected).abs() < 7.5e-8);
+        }
+    }
+
+    #[test]
+    fn test_cnd_tail_accuracy() {
+        // the polynomial's absolute error swamps the tail probabilities
+        for &(x, expected) in CND_REFERENCE[..2].iter() {
+            let fast = (cnd_with(x, CndPrecision::Fast) - expected).abs() / expected;
+            let accurate = (cnd_with(x, CndPrecision::Accurate) - expected).abs() / expected;
+            assert!(fast > 1e-4);
+            assert!(accurate < fast * 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_cbnd_independent() {
+        for &(a, b) in [(0.0, 0.0), (1.0, -0.5), (-2.0, 0.3)].iter() {
+            let abs = (cbnd(a, b, 0.0) - cnd(a) * cnd(b)).abs();
+            assert!(abs < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cbnd_origin() {
+        // orthant probability at the origin has the closed form 1/4 + asin(rho) / (2 pi)
+        for &rho in [-0.99_f64, -0.8, -0.5, -0.1, 0.2, 0.6, 0.9, 0.99].iter() {
+            let expected = 0.25 + rho.asin() / (2.0 * PI);
+            let abs = (cbnd(0.0, 0.0, rho) - expected).abs();
+            assert!(abs < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_cbnd_reference() {
+        // reference values from an independent numerical integration
+        let abs = (cbnd(0.5, -0.3, 0.5) - 0.3303585).abs();
+        assert!(abs < 1e-6);
+        let abs = (cbnd(1.2, 0.8, -0.95) - 0.6730749).abs();
+        assert!(abs < 1e-6);
+        let abs = (cbnd(-1.0, 1.5, 0.95) - 0.1586553).abs();
+        assert!(abs < 1e-6);
+    }
+
+    #[test]
+    fn test_sample_standard_normal_moments() {
+        let mut rng = SplitMix64(0x5EED);
+        let n = 200_000;
+        let samples: Vec<f64> = (0..n).map(|_| sample_standard_normal(&mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = samples.iter().map(|z| (z - mean).powf(2.0)).sum::<f64>() / (n - 1) as f64;
+        // standard errors are ~0.0022 for the mean and ~0.0032 for the variance
+        assert!(mean.abs() < 0.01);
+        assert!((variance - 1.0).abs() < 0.015);
+        // roughly 15.87% of draws should fall below -1
+        let below = samples.iter().filter(|&&z| z < -1.0).count() as f64 / n as f64;
+        assert!((below - cnd(-1.0)).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_inv_cnd() {
+        for &(x, p) in CND_REFERENCE.iter() {
+            let abs = (inv_cnd(p) - x).abs();
+            assert!(abs < 1e-9 * x.abs().max(1.0));
+        }
+        assert!(inv_cnd(0.5).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_strategy_sharpe() {
+        // monthly returns alternating 3% and -1% have mean 1% and sample standard deviation
+        // 2 * sqrt(4 / 3) %, about 2.31%
+        let samples = [0.03, -0.01, 0.03, -0.01];
+        let (mean, std) = (0.01, 0.02 * (4.0_f64 / 3.0).sqrt());
+        let sharpe = strategy_sharpe(&samples, 0.0, 12.0).unwrap();
+        assert!((sharpe - mean / std * 12.0_f64.sqrt()).abs() < 1e-12);
+
+        let excess = strategy_sharpe(&samples, 0.06, 12.0).unwrap();
+        assert!((excess - (mean - 0.005) / std * 12.0_f64.sqrt()).abs() < 1e-12);
+
+        // a single sample has no standard deviation
+        assert_eq!(strategy_sharpe(&[0.03], 0.0, 12.0), None);
+        assert_eq!(strategy_sharpe(&[], 0.0, 12.0), None);
+    }
+
+    #[test]
+    fn test_rolling_realized_vol() {
+        // steady growth realizes no volatility
+        let steady: Vec<f64> = (0..20).map(|i| 100.0 * 1.01_f64.powi(i)).collect();
+        let vols = rolling_realized_vol(&steady, 5, 365.0);
+        assert_eq!(vols.len(), 15);
+        assert!(vols.iter().all(|v| v.abs() < 1e-9));
+
+        // returns alternating +-1% over an even window have sample deviation 1% * sqrt(n / (n - 1))
+        let zigzag: Vec<f64> = (0..11)
+            .map(|i| if i % 2 == 0 { 100.0 } else { 101.0 })
+            .collect();
+        let a = (1.01_f64).ln();
+        let vol = rolling_realized_vol(&zigzag, 10, 365.0)[0];
+        assert!((vol - a * (10.0_f64 / 9.0 * 365.0).sqrt()).abs() < 1e-12);
+
+        assert!(rolling_realized_vol(&zigzag, 11, 365.0).is_empty());
+    }
+
+    #[test]
+    fn test_vol_cone() {
+        // daily log returns drawn at 60% annualized volatility
+        let sigma = 0.6;
+        let mut rng = SplitMix64(0x9E37_79B9_7F4A_7C15);
+        let mut prices = vec![100.0];
+        for _ in 0..3000 {
+            let z = sample_standard_normal(&mut rng);
+            let last = *prices.last().unwrap();
+            prices.push(last * (sigma / 365.0_f64.sqrt() * z).exp());
+        }
+        let windows = [10, 30, 90];
+        let cone = vol_cone(&prices, &windows, &[10.0, 50.0, 90.0], 365.0);
+        assert_eq!(cone.len(), windows.len());
+        for row in &cone {
+            assert!(row[0] < row[1] && row[1] < row[2]);
+            assert!((row[1] - sigma).abs() < 0.05);
+        }
+        // the cone narrows as the window lengthens
+        let width = |row: &Vec<f64>| row[2] - row[0];
+        assert!(width(&cone[0]) > width(&cone[1]) && width(&cone[1]) > width(&cone[2]));
+
+        // the extreme percentiles are the extreme realized vols
+        let vols = rolling_realized_vol(&prices, 30, 365.0);
+        let extremes = vol_cone(&prices, &[30], &[0.0, 100.0], 365.0);
+        let min = vols.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = vols.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!(extremes[0], vec![min, max]);
+
+        assert!(vol_cone(&prices[..5], &[10], &[50.0], 365.0)[0][0].is_nan());
+    }
+
+    #[test]
+    fn test_vol_cone_skips_nan_vols() {
+        // a missing print leaves NaN vols in every window which spans it
+        let mut prices: Vec<f64> = (0..40).map(|i| 100.0 + (i % 3) as f64).collect();
+        prices[20] = f64::NAN;
+        let vols = rolling_realized_vol(&prices, 5, 365.0);
+        assert!(vols.iter().any(|v| v.is_nan()));
+
+        let cone = vol_cone(&prices, &[5], &[0.0, 50.0, 100.0], 365.0);
+        assert!(cone[0].iter().all(|v| v.is_finite()));
+        let finite: Vec<f64> = vols.into_iter().filter(|v| v.is_finite()).collect();
+        let min = finite.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = finite.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!((cone[0][0], cone[0][2]), (min, max));
+    }
+
+    #[test]
+    #[should_panic(expected = "percentile = 101 must be between 0 and 100")]
+    fn test_vol_cone_percentile_out_of_range() {
+        let prices: Vec<f64> = (0..40).map(|i| 100.0 + (i % 3) as f64).collect();
+        vol_cone(&prices, &[5], &[101.0], 365.0);
+    }
+}
@@ -0,0 +1,78 @@
+// Test-only sample market shared by the unit tests, with the reference values of the standard greeks
+// Test modules take their inputs from here rather than declaring their own, apart from the exotic pricers
+// checked against textbook examples, and the expected values match the modules' `E_*` constants to four decimals.
+
+// Inputs of a sample European option and its reference prices and greeks
+pub struct SampleParams {
+    pub s0: f64,
+    pub x: f64,
+    pub t: f64,
+    pub r: f64,
+    pub q: f64,
+    pub sigma: f64,
+    pub days_per_year: f64,
+    pub call_price: f64,
+    pub put_price: f64,
+    pub call_delta: f64,
+    pub put_delta: f64,
+    pub gamma: f64,
+    // per volatility point
+    pub vega: f64,
+    // per calendar day
+    pub theta_call: f64,
+    pub theta_put: f64,
+    // per percentage point
+    pub rho_call: f64,
+    pub rho_put: f64,
+}
+
+pub const SAMPLE_PARAMS: SampleParams = SampleParams {
+    s0: 64.68,
+    x: 65.00,
+    t: 23.0 / 365.0,
+    r: 0.0150,
+    q: 0.0210,
+    sigma: 0.5051,
+    days_per_year: 365.0,
+    call_price: 3.105,
+    put_price: 3.449,
+    call_delta: 0.5079,
+    put_delta: -0.4908,
+    gamma: 0.0486,
+    vega: 0.0647,
+    theta_call: -0.0703,
+    theta_put: -0.0714,
+    rho_call: 0.0187,
+    rho_put: -0.0222,
+};
+
+#[cfg(test)]
+mod tests {
+    use greeks::*;
+    use price::{euro_call, euro_put};
+    use test_fixtures::SAMPLE_PARAMS;
+
+    #[test]
+    fn test_sample_params_reproduce_greeks() {
+        let p = SAMPLE_PARAMS;
+        let (s, x, t, r, q, v) = (p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let close = |actual: f64, expected: f64| (actual - expected).abs() < 0.001;
+
+        assert!(close(euro_call(s, x, t, r, q, v), p.call_price));
+        assert!(close(euro_put(s, x, t, r, q, v), p.put_price));
+        assert!(close(delta_call(s, x, t, r, q, v), p.call_delta));
+        assert!(close(delta_put(s, x, t, r, q, v), p.put_delta));
+        assert!(close(gamma(s, x, t, r, q, v), p.gamma));
+        assert!(close(vega(s, x, t, r, q, v), p.vega));
+        assert!(close(
+            theta_call(s, x, t, r, q, v, p.days_per_year),
+            p.theta_call
+        ));
+        assert!(close(
+            theta_put(s, x, t, r, q, v, p.days_per_year),
+            p.theta_put
+        ));
+        assert!(close(rho_call(s, x, t, r, q, v), p.rho_call));
+        assert!(close(rho_put(s, x, t, r, q, v), p.rho_put));
+    }
+}
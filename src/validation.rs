@@ -0,0 +1,230 @@
+// Module containing checks on pricing inputs and computed greeks
+use common::OptionType;
+use greeks::Greeks;
+
+/// Lists the inputs which look like they were passed in the wrong units
+///
+/// Flags a volatility above 5 (likely a percentage such as `50` for 50%), a time to expiry above 100
+/// (likely days rather than years) and rates or yields above 1 in magnitude (likely percentages).
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn input_warnings(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if sigma > 5.0 {
+        warnings.push(format!(
+            "sigma = {} looks like a percentage, volatility is a decimal",
+            sigma
+        ));
+    }
+    if t > 100.0 {
+        warnings.push(format!(
+            "t = {} looks like days, time to expiry is in years",
+            t
+        ));
+    }
+    if r.abs() > 1.0 {
+        warnings.push(format!(
+            "r = {} looks like a percentage, rates are decimals",
+            r
+        ));
+    }
+    if q.abs() > 1.0 {
+        warnings.push(format!(
+            "q = {} looks like a percentage, yields are decimals",
+            q
+        ));
+    }
+    if s0 / x > 1000.0 || x / s0 > 1000.0 {
+        warnings.push(format!(
+            "s0 = {} and x = {} differ by over 1000x, check the quote currency",
+            s0, x
+        ));
+    }
+    warnings
+}
+
+/// Checks the inputs lie in the domain of the Black-Scholes formulas
+///
+/// Prices, time and volatility must be strictly positive and every input finite.
+///
+/// # Arguments
+/// * `s0` - The underlying price of the option
+/// * `x` - The strike price of the option
+/// * `t` - time to expiration as a percentage of the year
+/// * `r` - continuously compounded risk-free interest rate
+/// * `q` - continuously compounded divident yield
+/// * `sigma` - volatility
+pub fn validate_inputs(s0: f64, x: f64, t: f64, r: f64, q: f64, sigma: f64) -> Result<(), String> {
+    let inputs = [
+        ("s0", s0),
+        ("x", x),
+        ("t", t),
+        ("r", r),
+        ("q", q),
+        ("sigma", sigma),
+    ];
+    for &(name, value) in inputs.iter() {
+        if !value.is_finite() {
+            return Err(format!("{} must be finite, got {}", name, value));
+        }
+    }
+    for &(name, value) in [("s0", s0), ("x", x), ("t", t), ("sigma", sigma)].iter() {
+        if value <= 0.0 {
+            return Err(format!("{} must be positive, got {}", name, value));
+        }
+    }
+    Ok(())
+}
+
+/// Theoretical range of each greek of a European option, as (lowest, highest)
+///
+/// Units follow `Greeks`. Theta has no sign in general, deep in the money puts and calls on high yielding
+/// underlyings gain value as time passes, so it is only required to be finite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GreekBounds {
+    pub call_delta: (f64, f64),
+    pub put_delta: (f64, f64),
+    pub gamma: (f64, f64),
+    pub theta: (f64, f64),
+    pub vega: (f64, f64),
+    pub call_rho: (f64, f64),
+    pub put_rho: (f64, f64),
+}
+
+/// Returns the theoretical bounds of the greeks of a European option under Black-Scholes
+///
+/// Call delta lies in `[0, 1]` and put delta in `[-1, 0]` for a non-negative dividend yield, gamma and vega are
+/// never negative, and a call gains from higher rates while a put loses.
+pub fn greek_bounds() -> GreekBounds {
+    GreekBounds {
+        call_delta: (0.0, 1.0),
+        put_delta: (-1.0, 0.0),
+        gamma: (0.0, f64::INFINITY),
+        theta: (f64::NEG_INFINITY, f64::INFINITY),
+        vega: (0.0, f64::INFINITY),
+        call_rho: (0.0, f64::INFINITY),
+        put_rho: (f64::NEG_INFINITY, 0.0),
+    }
+}
+
+/// Checks a computed bundle of greeks lies within the theoretical bounds of `greek_bounds`
+///
+/// Every greek must also be finite. The error names the first greek out of range.
+///
+/// # Arguments
+/// * `greeks` - The greeks of one option
+/// * `kind` - Whether the option is a call or a put
+pub fn validate_greeks(greeks: &Greeks, kind: OptionType) -> Result<(), String> {
+    let bounds = greek_bounds();
+    let (delta, rho) = match kind {
+        OptionType::Call => (bounds.call_delta, bounds.call_rho),
+        OptionType::Put => (bounds.put_delta, bounds.put_rho),
+    };
+    let checks = [
+        ("delta", greeks.delta, delta),
+        ("gamma", greeks.gamma, bounds.gamma),
+        ("theta", greeks.theta, bounds.theta),
+        ("vega", greeks.vega, bounds.vega),
+        ("rho", greeks.rho, rho),
+    ];
+    for &(name, value, (lo, hi)) in checks.iter() {
+        if !value.is_finite() {
+            return Err(format!("{} must be finite, got {}", name, value));
+        }
+        if value < lo || value > hi {
+            return Err(format!(
+                "{} must be in [{}, {}], got {}",
+                name, lo, hi, value
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::OptionType;
+    use greeks::{greeks_call, greeks_put, Greeks};
+    use test_fixtures::SAMPLE_PARAMS;
+    use validation::*;
+
+    #[test]
+    fn test_input_warnings_clean() {
+        let p = SAMPLE_PARAMS;
+        let warnings = input_warnings(p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_input_warnings_flagged() {
+        let p = SAMPLE_PARAMS;
+        let sigma = input_warnings(p.s0, p.x, p.t, p.r, p.q, 50.0);
+        assert_eq!(sigma.len(), 1);
+        assert!(sigma[0].starts_with("sigma"));
+
+        let t = input_warnings(p.s0, p.x, 23.0 * 7.0, p.r, p.q, p.sigma);
+        assert_eq!(t.len(), 1);
+        assert!(t[0].starts_with("t ="));
+
+        let rates = input_warnings(p.s0, p.x, p.t, 1.5, 2.1, p.sigma);
+        assert_eq!(rates.len(), 2);
+
+        let currency = input_warnings(p.s0, p.x * 1e6, p.t, p.r, p.q, p.sigma);
+        assert_eq!(currency.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_inputs() {
+        let p = SAMPLE_PARAMS;
+        assert!(validate_inputs(p.s0, p.x, p.t, p.r, p.q, p.sigma).is_ok());
+        assert!(validate_inputs(p.s0, p.x, 0.0, p.r, p.q, p.sigma).is_err());
+        assert!(validate_inputs(p.s0, p.x, p.t, f64::NAN, p.q, p.sigma).is_err());
+    }
+
+    #[test]
+    fn test_validate_greeks() {
+        let p = SAMPLE_PARAMS;
+        let call = greeks_call(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
+        let put = greeks_put(p.s0, p.x, p.t, p.r, p.q, p.sigma, p.days_per_year);
+        assert_eq!(validate_greeks(&call, OptionType::Call), Ok(()));
+        assert_eq!(validate_greeks(&put, OptionType::Put), Ok(()));
+        // a call's greeks are not a put's
+        assert!(validate_greeks(&call, OptionType::Put).is_err());
+
+        let negative_gamma = Greeks {
+            gamma: -call.gamma,
+            ..call
+        };
+        let err = validate_greeks(&negative_gamma, OptionType::Call).unwrap_err();
+        assert!(err.starts_with("gamma"));
+
+        let levered = Greeks { delta: 1.2, ..call };
+        assert!(validate_greeks(&levered, OptionType::Call)
+            .unwrap_err()
+            .starts_with("delta"));
+
+        let positive_rho = Greeks {
+            rho: -put.rho,
+            ..put
+        };
+        assert!(validate_greeks(&positive_rho, OptionType::Put)
+            .unwrap_err()
+            .starts_with("rho"));
+
+        let nan_theta = Greeks {
+            theta: f64::NAN,
+            ..call
+        };
+        assert_eq!(
+            validate_greeks(&nan_theta, OptionType::Call),
+            Err("theta must be finite, got NaN".to_string())
+        );
+    }
+}
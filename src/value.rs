@@ -1,3 +1,6 @@
+use common::{forward_price, OptionType};
+use contract::OptionContract;
+
 /// Calculates the value of a call option at Expiry
 ///
 /// # Arguments
@@ -19,3 +22,158 @@ pub fn put_at_expiry(s_t: f64, x: f64) -> f64 {
     let res = x - s_t;
     return if res > 0.0 { res } else { 0.0 };
 }
+
+/// Calculates the underlying price at expiry where a long option recovers its premium
+///
+/// # Arguments
+///
+/// `kind` - Whether the option is a call or a put
+/// `x` - Option strike price
+/// `premium` - Price paid for the option
+pub fn break_even(kind: OptionType, x: f64, premium: f64) -> f64 {
+    match kind {
+        OptionType::Call => x + premium,
+        OptionType::Put => x - premium,
+    }
+}
+
+/// Calculates the underlying price at expiry where a long option recovers its premium including the cost of financing it
+///
+/// The premium is paid today, so it is compounded to expiry at the risk-free rate before being recovered.
+///
+/// # Arguments
+///
+/// `kind` - Whether the option is a call or a put
+/// `x` - Option strike price
+/// `premium` - Price paid for the option
+/// `t` - time to expiration as a percentage of the year
+/// `r` - continuously compounded risk-free interest rate
+pub fn break_even_financed(kind: OptionType, x: f64, premium: f64, t: f64, r: f64) -> f64 {
+    break_even(kind, x, premium * (r * t).exp())
+}
+
+/// Calculates the risk-neutral expected price of the underlying at expiry
+///
+/// Under the pricing measure the underlying drifts at the cost of carry, so the expected terminal spot is the forward price.
+///
+/// # Arguments
+///
+/// `s0` - The underlying spot price
+/// `t` - time to expiration as a percentage of the year
+/// `r` - continuously compounded risk-free interest rate
+/// `q` - continuously compounded divident yield
+pub fn expected_terminal_spot(s0: f64, t: f64, r: f64, q: f64) -> f64 {
+    forward_price(s0, t, r, q)
+}
+
+/// Calculates the real-world expected price of the underlying at expiry
+///
+/// # Arguments
+///
+/// `s0` - The underlying spot price
+/// `t` - time to expiration as a percentage of the year
+/// `mu` - expected continuously compounded total return of the underlying
+/// `q` - continuously compounded divident yield
+pub fn expected_terminal_spot_real_world(s0: f64, t: f64, mu: f64, q: f64) -> f64 {
+    s0 * ((mu - q) * t).exp()
+}
+
+/// Estimates the expected profit and loss of holding an option over a short period
+///
+/// Takes the expectation of the second order expansion `theta dt + delta dS + gamma dS^2 / 2` under a real-world
+/// drift: the spot is expected to move by `(mu - q) * s0 * dt` and its squared move is `sigma_realized^2 * s0^2 * dt`.
+/// When realized volatility matches implied the gamma term pays for the volatility part of theta, so
+/// only carry remains. Accurate while the holding period is short relative to expiry.
+///
+/// # Arguments
+///
+/// `contract` - The option held
+/// `mu` - expected continuously compounded total return of the underlying
+/// `holding_t` - holding period as a percentage of the year
+/// `sigma_realized` - volatility realized over the holding period
+/// `days_per_year` - the number of calendar days in the year
+pub fn expected_pnl(
+    contract: &OptionContract,
+    mu: f64,
+    holding_t: f64,
+    sigma_realized: f64,
+    days_per_year: f64,
+) -> f64 {
+    let greeks = contract.greeks(days_per_year);
+    let theta_pnl = greeks.theta * holding_t * days_per_year;
+    let delta_pnl = greeks.delta * contract.s0 * (mu - contract.q) * holding_t;
+    let gamma_pnl =
+        0.5 * greeks.gamma * contract.s0.powf(2.0) * sigma_realized.powf(2.0) * holding_t;
+    theta_pnl + delta_pnl + gamma_pnl
+}
+
+#[cfg(test)]
+mod tests {
+
+    use common::{forward_price, OptionType};
+    use contract::OptionContract;
+    use test_fixtures::SAMPLE_PARAMS;
+    use value::*;
+
+    #[test]
+    fn test_expected_terminal_spot() {
+        let p = SAMPLE_PARAMS;
+        let expected = expected_terminal_spot(p.s0, p.t, p.r, p.q);
+        let forward = forward_price(p.s0, p.t, p.r, p.q);
+        assert!((expected - forward).abs() < 1e-12);
+        // carry is negative here, so the expected spot sits below today's spot
+        assert!(expected < p.s0);
+    }
+
+    #[test]
+    fn test_expected_terminal_spot_real_world() {
+        let p = SAMPLE_PARAMS;
+        let mu = 0.25;
+        let real_world = expected_terminal_spot_real_world(p.s0, p.t, mu, p.q);
+        let risk_neutral = expected_terminal_spot_real_world(p.s0, p.t, p.r, p.q);
+        let forward = forward_price(p.s0, p.t, p.r, p.q);
+        assert!((risk_neutral - forward).abs() < 1e-12);
+        assert!(real_world > forward);
+    }
+
+    #[test]
+    fn test_break_even_financed() {
+        let (strike, premium, t, r) = (65.0, 12.0, 2.0, 0.05);
+        let call = break_even(OptionType::Call, strike, premium);
+        let call_financed = break_even_financed(OptionType::Call, strike, premium, t, r);
+        let put = break_even(OptionType::Put, strike, premium);
+        let put_financed = break_even_financed(OptionType::Put, strike, premium, t, r);
+
+        assert!((call - 77.0).abs() < 1e-12);
+        assert!((put - 53.0).abs() < 1e-12);
+        // two years of financing at 5% adds about 10.5% to the premium
+        assert!((call_financed - 78.2612).abs() < 0.001);
+        assert!((put_financed - 51.7388).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_expected_pnl_theta_only() {
+        let p = SAMPLE_PARAMS;
+        // with no drift and no realized volatility the option simply decays
+        let contract = OptionContract::new(OptionType::Call, p.s0, p.x, p.t, p.r, p.q, p.sigma);
+        let week = 7.0 / p.days_per_year;
+        let pnl = expected_pnl(&contract, p.q, week, 0.0, p.days_per_year);
+        let theta = contract.greeks(p.days_per_year).theta;
+        assert!((pnl - 7.0 * theta).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_expected_pnl_realized_equals_implied() {
+        let p = SAMPLE_PARAMS;
+        // without carry, gamma earned at the implied volatility exactly pays for theta
+        let contract = OptionContract::new(OptionType::Put, p.s0, p.x, p.t, 0.0, 0.0, p.sigma);
+        let week = 7.0 / p.days_per_year;
+        let pnl = expected_pnl(&contract, 0.0, week, p.sigma, p.days_per_year);
+        let theta = contract.greeks(p.days_per_year).theta;
+        assert!(pnl.abs() < 1e-12);
+        assert!(theta < -0.01);
+
+        let rich = expected_pnl(&contract, 0.0, week, 1.5 * p.sigma, p.days_per_year);
+        assert!(rich > 0.0);
+    }
+}
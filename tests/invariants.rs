@@ -0,0 +1,104 @@
+// Structural properties of the pricing functions and greeks checked over a parameter grid
+extern crate greeks;
+
+use greeks::*;
+
+const STRIKE: f64 = 100.0;
+const INTEREST_RATE: f64 = 0.03;
+const DAYS_PER_YEAR: f64 = 365.0;
+
+const SPOTS: [f64; 9] = [50.0, 70.0, 85.0, 95.0, 100.0, 105.0, 115.0, 130.0, 150.0];
+const VOLS: [f64; 5] = [0.1, 0.25, 0.5, 0.8, 1.2];
+const EXPIRIES: [f64; 4] = [7.0 / DAYS_PER_YEAR, 0.25, 1.0, 2.0];
+const DIV_YIELDS: [f64; 3] = [0.0, 0.05, 0.2];
+
+// allow for the error of the cumulative normal approximation
+const TOLERANCE: f64 = 1e-6;
+
+fn for_each_market<F: Fn(f64, f64, f64, f64)>(f: F) {
+    for &s0 in SPOTS.iter() {
+        for &sigma in VOLS.iter() {
+            for &t in EXPIRIES.iter() {
+                for &q in DIV_YIELDS.iter() {
+                    f(s0, t, q, sigma);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_price_monotonic_in_spot() {
+    for &sigma in VOLS.iter() {
+        for &t in EXPIRIES.iter() {
+            for &q in DIV_YIELDS.iter() {
+                for pair in SPOTS.windows(2) {
+                    let (lo, hi) = (pair[0], pair[1]);
+                    let call_lo = euro_call(lo, STRIKE, t, INTEREST_RATE, q, sigma);
+                    let call_hi = euro_call(hi, STRIKE, t, INTEREST_RATE, q, sigma);
+                    let put_lo = euro_put(lo, STRIKE, t, INTEREST_RATE, q, sigma);
+                    let put_hi = euro_put(hi, STRIKE, t, INTEREST_RATE, q, sigma);
+                    assert!(call_hi > call_lo - TOLERANCE);
+                    assert!(put_hi < put_lo + TOLERANCE);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_price_monotonic_in_vol() {
+    for &s0 in SPOTS.iter() {
+        for &t in EXPIRIES.iter() {
+            for &q in DIV_YIELDS.iter() {
+                for pair in VOLS.windows(2) {
+                    let (lo, hi) = (pair[0], pair[1]);
+                    let call_lo = euro_call(s0, STRIKE, t, INTEREST_RATE, q, lo);
+                    let call_hi = euro_call(s0, STRIKE, t, INTEREST_RATE, q, hi);
+                    let put_lo = euro_put(s0, STRIKE, t, INTEREST_RATE, q, lo);
+                    let put_hi = euro_put(s0, STRIKE, t, INTEREST_RATE, q, hi);
+                    assert!(call_hi > call_lo - TOLERANCE);
+                    assert!(put_hi > put_lo - TOLERANCE);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_delta_bounds() {
+    for_each_market(|s0, t, q, sigma| {
+        let call = delta_call(s0, STRIKE, t, INTEREST_RATE, q, sigma);
+        let put = delta_put(s0, STRIKE, t, INTEREST_RATE, q, sigma);
+        assert!((0.0..=1.0).contains(&call));
+        assert!((-1.0..=0.0).contains(&put));
+    });
+}
+
+#[test]
+fn test_call_theta_negative_without_dividends() {
+    for &s0 in SPOTS.iter() {
+        for &sigma in VOLS.iter() {
+            for &t in EXPIRIES.iter() {
+                let theta = theta_call(s0, STRIKE, t, INTEREST_RATE, 0.0, sigma, DAYS_PER_YEAR);
+                assert!(theta < TOLERANCE);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_theta_matches_time_decay() {
+    // theta is the negative time derivative of the price, including for large dividend yields
+    let h = 1e-5;
+    for_each_market(|s0, t, q, sigma| {
+        let decay =
+            |price: &dyn Fn(f64) -> f64| -(price(t + h) - price(t - h)) / (2.0 * h) / DAYS_PER_YEAR;
+        let call = decay(&|t| euro_call(s0, STRIKE, t, INTEREST_RATE, q, sigma));
+        let put = decay(&|t| euro_put(s0, STRIKE, t, INTEREST_RATE, q, sigma));
+        let theta_call = theta_call(s0, STRIKE, t, INTEREST_RATE, q, sigma, DAYS_PER_YEAR);
+        let theta_put = theta_put(s0, STRIKE, t, INTEREST_RATE, q, sigma, DAYS_PER_YEAR);
+        assert!((theta_call - call).abs() < 1e-4);
+        assert!((theta_put - put).abs() < 1e-4);
+    });
+}